@@ -0,0 +1,111 @@
+//! Marvel Snap can also export a player's whole collection using the same
+//! encoding as decks. A [`Collection`] holds any number of cards and has no
+//! name, which keeps it apart from a playable [`DeckList`](crate::DeckList).
+
+use crate::{decode_json, Card, DeckListError};
+use base64::{engine::general_purpose, Engine};
+use serde_derive::Deserialize;
+use serde_derive::Serialize;
+use std::collections::HashSet;
+
+/// The cards a player owns
+///
+/// # Example
+///
+/// ```rust
+/// use marvelsnapdeck::collection::Collection;
+///
+/// let mut collection = Collection::new();
+/// collection.set_cards(&["AntMan", "Thanos"]);
+/// let code = collection.into_code().unwrap();
+///
+/// let decoded = Collection::from_code(code).unwrap();
+/// assert_eq!(decoded.cards(), vec!["AntMan", "Thanos"]);
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Collection {
+    #[serde(rename = "Cards")]
+    cards: Vec<Card>,
+}
+
+impl Collection {
+    /// Create an empty Collection
+    pub fn new() -> Self {
+        Self {
+            cards: Default::default(),
+        }
+    }
+
+    /// Set the list of owned cards.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::collection::Collection;
+    ///
+    /// let mut collection = Collection::new();
+    /// collection.set_cards(&["AntMan"]);
+    ///
+    /// assert_eq!(collection.cards()[0], "AntMan");
+    /// ```
+    pub fn set_cards<T: AsRef<str> + std::fmt::Display>(&mut self, cards: &[T]) {
+        let list = cards
+            .iter()
+            .map(|name| Card {
+                name: name.to_string(),
+                meta: None,
+            })
+            .collect();
+        self.cards = list;
+    }
+
+    /// Get list of owned cards as a vector of strings
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::collection::Collection;
+    ///
+    /// let mut collection = Collection::new();
+    /// collection.set_cards(&["AntMan"]);
+    ///
+    /// assert_eq!(collection.cards(), vec!["AntMan"]);
+    /// ```
+    pub fn cards(&self) -> Vec<String> {
+        self.cards.iter().map(|card| card.name.clone()).collect()
+    }
+
+    /// Get the owned cards as a set, as used by [`DeckList::owned_count`](crate::DeckList::owned_count)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::collection::Collection;
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut collection = Collection::new();
+    /// collection.set_cards(&["AntMan", "Thanos"]);
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan", "Lockjaw"]);
+    ///
+    /// assert_eq!(list.owned_count(&collection.owned()), 1);
+    /// ```
+    pub fn owned(&self) -> HashSet<String> {
+        self.cards.iter().map(|card| card.name.clone()).collect()
+    }
+
+    /// Convert a collection code exported from Marvel Snap into a Collection.
+    pub fn from_code<T: AsRef<[u8]>>(code: T) -> Result<Self, DeckListError> {
+        decode_json(code.as_ref(), &general_purpose::STANDARD_NO_PAD)
+    }
+
+    /// Converts Collection into a code using the same encoding as decks
+    pub fn into_code(&self) -> Result<String, DeckListError> {
+        let data = serde_json::to_vec(self).map_err(|_| DeckListError::EncodingError)?;
+
+        let code = general_purpose::STANDARD_NO_PAD.encode(data);
+
+        Ok(code)
+    }
+}
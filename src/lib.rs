@@ -6,11 +6,18 @@
 #![warn(missing_docs, missing_debug_implementations, rust_2018_idioms)]
 
 use base64::DecodeError;
-use base64::{engine::general_purpose, Engine as _};
+use base64::{engine::general_purpose, Engine};
+use serde::de::DeserializeOwned;
 use serde_derive::Deserialize;
 use serde_derive::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use thiserror::Error;
 
+pub mod collection;
+
+/// Number of cards in a complete Marvel Snap deck
+pub const DECK_SIZE: usize = 12;
+
 /// List of errors returned from a Result
 #[derive(Debug, Error)]
 pub enum DeckListError {
@@ -26,6 +33,136 @@ pub enum DeckListError {
     /// Likely a bad code, this is a common error and should fail gracefully
     #[error("Invalid data")]
     InvalidDeckInput,
+
+    /// The code holds an array of several decks, use [`DeckList::many_from_code`] instead
+    #[error("Code contains multiple decks")]
+    MultipleDecks,
+
+    /// The deck name contains control characters or newlines the game does not accept
+    #[error("Invalid deck name")]
+    InvalidName,
+
+    /// The input is longer than the limit given to [`DeckList::from_code_limited`]
+    #[error("Input too large")]
+    InputTooLarge,
+
+    /// The deck has no name, which the game requires
+    #[error("Deck name is empty")]
+    EmptyName,
+
+    /// A complete deck needs exactly [`DECK_SIZE`] cards
+    #[error("Deck has {0} cards instead of {}", DECK_SIZE)]
+    WrongCardCount(usize),
+
+    /// The same card can only be in a deck once
+    #[error("Card {0} appears more than once")]
+    DuplicateCard(String),
+
+    /// The card is not in the registry given to [`DeckList::to_bitset`]
+    #[error("Card {0} is not in the registry")]
+    UnknownCard(String),
+
+    /// A tracker export holds more than one copy of a card, see [`DeckList::from_tracker_json`]
+    #[error("Card {0} has a count of {1} but decks hold one copy of each card")]
+    CardCountTooHigh(String, usize),
+
+    /// A row of the CSV given to [`decks_from_csv`] is malformed
+    #[error("Line {line}: {msg}")]
+    ParseError {
+        /// One-based line number of the offending row
+        line: usize,
+        /// What was wrong with the row
+        msg: String,
+    },
+
+    /// The code stored under a key of a named map could not be read, see [`from_named_map_json`]
+    #[error("Deck {key} is invalid: {source}")]
+    InvalidEntry {
+        /// The key of the entry that failed
+        key: String,
+        /// Why the entry failed to decode
+        #[source]
+        source: Box<DeckListError>,
+    },
+
+    /// Compressed data could not be inflated, only returned by `from_code_compressed`
+    /// with the `compress` feature
+    #[error("Failed to decompress data")]
+    DecompressionError,
+
+    /// The separator given to [`parse_named_code`] can appear inside a code
+    #[error("Separator {0:?} can appear inside a code")]
+    InvalidSeparator(char),
+}
+
+impl DeckListError {
+    /// A friendly sentence explaining the error to players, with what to try next.
+    ///
+    /// [`Display`](std::fmt::Display) stays the short message meant for developers.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let error = DeckList::from_code("not a code!").unwrap_err();
+    ///
+    /// assert_eq!(
+    ///     error.user_message(),
+    ///     "The code contains invalid characters, check you copied the whole thing."
+    /// );
+    /// ```
+    pub fn user_message(&self) -> String {
+        match self {
+            DeckListError::EncodingError => {
+                "The deck could not be turned into a code, please try again.".to_string()
+            }
+            DeckListError::DecodingError(_) => {
+                "The code contains invalid characters, check you copied the whole thing."
+                    .to_string()
+            }
+            DeckListError::InvalidDeckInput => {
+                "This doesn't look like a deck code, copy it again from the game.".to_string()
+            }
+            DeckListError::MultipleDecks => {
+                "This code holds several decks, import it as a deck collection instead.".to_string()
+            }
+            DeckListError::InvalidName => {
+                "The deck name can't contain line breaks or special control characters.".to_string()
+            }
+            DeckListError::InputTooLarge => {
+                "This is too long to be a deck code, check you copied only the code.".to_string()
+            }
+            DeckListError::EmptyName => "Give the deck a name before sharing it.".to_string(),
+            DeckListError::WrongCardCount(count) => {
+                format!("A deck needs exactly {DECK_SIZE} cards, this one has {count}.")
+            }
+            DeckListError::DuplicateCard(card) => {
+                format!("{card} is in the deck more than once, remove the extra copy.")
+            }
+            DeckListError::UnknownCard(card) => {
+                format!("{card} isn't a known card, check the deck for typos.")
+            }
+            DeckListError::CardCountTooHigh(card, count) => {
+                format!("{card} is listed {count} times, but a deck holds one copy of each card.")
+            }
+            DeckListError::ParseError { line, msg } => {
+                format!("Line {line} of the file couldn't be read: {msg}.")
+            }
+            DeckListError::InvalidEntry { key, source } => {
+                format!(
+                    "The deck \"{key}\" couldn't be imported. {}",
+                    source.user_message()
+                )
+            }
+            DeckListError::DecompressionError => {
+                "This compressed code is damaged, check you copied the whole thing.".to_string()
+            }
+            DeckListError::InvalidSeparator(sep) => {
+                format!("'{sep}' can appear inside a deck code, pick another separator.")
+            }
+        }
+    }
 }
 
 /// The game Marvel Snap allows sharing decks through the use of encoded strings.
@@ -56,21 +193,135 @@ pub enum DeckListError {
 /// let clipboard = "...";
 /// let mut list = DeckList::from_code(clipboard);
 /// ```
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DeckList {
     #[serde(rename = "Name")]
     name: String,
     #[serde(rename = "Cards")]
     cards: Vec<Card>,
+    #[serde(skip)]
+    canonical_ordering: bool,
+    #[serde(skip)]
+    source_tag: Option<String>,
+    #[serde(skip)]
+    extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// An individual card
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Card {
     #[serde(rename = "CardDefId")]
     name: String,
+    #[serde(skip)]
+    meta: Option<Box<CardMeta>>,
+}
+
+/// Extra information tools can attach to a card, never included in codes
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CardMeta {
+    /// Whether the player marked the card as a favorite
+    pub favorite: bool,
+    /// Free form notes about the card
+    pub notes: String,
+}
+
+/// Shapes of JSON accepted inside a code, some tools wrap decks in an array
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Payload {
+    One(DeckList),
+    Many(Vec<DeckList>),
+}
+
+/// The simplified `{"name": "...", "cards": ["AntMan"]}` schema used by many tools
+#[derive(Serialize, Deserialize)]
+struct FlatDeck {
+    #[serde(default)]
+    name: String,
+    cards: Vec<String>,
+}
+
+/// The `{"deckName": "...", "cards": [{"cardDefId": "AntMan", "count": 1}]}` tracker export
+#[derive(Deserialize)]
+struct TrackerDeck {
+    #[serde(rename = "deckName", default)]
+    name: String,
+    cards: Vec<TrackerCard>,
+}
+
+#[derive(Deserialize)]
+struct TrackerCard {
+    #[serde(rename = "cardDefId")]
+    name: String,
+    #[serde(default = "one")]
+    count: usize,
+}
+
+fn one() -> usize {
+    1
+}
+
+/// Deck JSON which leaves out the name entirely when it is empty
+#[derive(Serialize)]
+struct MinimalDeck<'a> {
+    #[serde(rename = "Name", skip_serializing_if = "str::is_empty")]
+    name: &'a str,
+    #[serde(rename = "Cards")]
+    cards: &'a [Card],
+}
+
+/// Deck JSON with the tool-only source tag added, for storage
+#[derive(Serialize)]
+struct TaggedDeck<'a> {
+    #[serde(flatten)]
+    deck: &'a DeckList,
+    #[serde(rename = "SourceTag", skip_serializing_if = "Option::is_none")]
+    source_tag: Option<&'a str>,
+}
+
+/// Deck JSON followed by extra fields carried forward for the game
+#[derive(Serialize)]
+struct ExtraDeck<'a> {
+    #[serde(flatten)]
+    deck: &'a DeckList,
+    /// Sorted here, as `serde_json::Map` keeps insertion order with `preserve_order`
+    #[serde(flatten)]
+    extra: BTreeMap<&'a str, &'a serde_json::Value>,
+}
+
+/// Changes from an earlier version of a deck, see [`DeckList::change_summary`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangeSummary {
+    /// Whether the deck was renamed
+    pub name_changed: bool,
+    /// Cards added since the earlier version, sorted by name
+    pub added: Vec<String>,
+    /// Cards removed since the earlier version, sorted by name
+    pub removed: Vec<String>,
+    /// Change in the total number of cards
+    pub net_card_delta: isize,
+}
+
+/// Lengths of a deck's code under different encodings, see [`DeckList::code_size_report`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodeSizeReport {
+    /// Length of [`DeckList::into_code`]
+    pub standard: usize,
+    /// Length of [`DeckList::canonical_code`], with cards sorted by name
+    pub canonical: usize,
+    /// Length of the code without the `Name` field, as in [`DeckList::to_minimal_json`]
+    pub nameless: usize,
+}
+
+/// Cards added and removed between two decks, see [`DeckList::diff`]
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DeckDiff {
+    /// Cards in the other deck that are missing from this one
+    pub added: Vec<String>,
+    /// Cards in this deck that are missing from the other one
+    pub removed: Vec<String>,
 }
 
 impl DeckList {
@@ -79,6 +330,9 @@ impl DeckList {
         Self {
             name: Default::default(),
             cards: Default::default(),
+            canonical_ordering: false,
+            source_tag: None,
+            extra: serde_json::Map::new(),
         }
     }
 
@@ -98,6 +352,115 @@ impl DeckList {
         self.name = name;
     }
 
+    /// A copy of the deck with the same cards under a new name, for "save as" flows
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_name("Thanos".into());
+    /// list.set_cards(&["AntMan"]);
+    ///
+    /// let copy = list.clone_with_name("Thanos v2");
+    ///
+    /// assert_eq!(copy.name(), "Thanos v2");
+    /// assert_eq!(copy.cards(), list.cards());
+    /// ```
+    pub fn clone_with_name(&self, name: impl Into<String>) -> DeckList {
+        let mut list = self.clone();
+        list.name = name.into();
+        list
+    }
+
+    /// Give the deck a generated name if it has none.
+    ///
+    /// The generated name is `Deck-` followed by the [`DeckList::short_id`], so decks
+    /// with the same cards get the same name. Existing names are left alone.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan", "Thanos"]);
+    /// list.ensure_name();
+    ///
+    /// assert_eq!(list.name(), format!("Deck-{}", list.short_id()));
+    /// ```
+    pub fn ensure_name(&mut self) {
+        if self.name.is_empty() {
+            self.name = format!("Deck-{}", self.short_id());
+        }
+    }
+
+    /// Set the deck name, rejecting names the game would not accept
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    ///
+    /// assert!(list.try_set_name("Thanos").is_ok());
+    /// assert!(list.try_set_name("Than\nos").is_err());
+    /// assert_eq!(list.name(), "Thanos");
+    /// ```
+    pub fn try_set_name(&mut self, name: impl Into<String>) -> Result<(), DeckListError> {
+        let name = name.into();
+        if !is_valid_name(&name) {
+            return Err(DeckListError::InvalidName);
+        }
+
+        self.name = name;
+        Ok(())
+    }
+
+    /// Check the deck name has no control characters or newlines
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_name("Than\tos".into());
+    ///
+    /// assert!(!list.name_is_valid());
+    /// ```
+    pub fn name_is_valid(&self) -> bool {
+        is_valid_name(&self.name)
+    }
+
+    /// Check the deck name needs no escaping in JSON.
+    ///
+    /// Names with quotes, backslashes or control characters are encoded correctly by
+    /// this crate, but can corrupt the JSON of tools that build it by concatenating
+    /// strings. Use this to warn before handing a name to such tools.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_name(r#"The "Mad" Titan"#.into());
+    ///
+    /// assert!(!list.name_is_json_safe());
+    ///
+    /// let code = list.into_code().unwrap();
+    /// assert_eq!(DeckList::from_code(&code).unwrap().name(), r#"The "Mad" Titan"#);
+    /// ```
+    pub fn name_is_json_safe(&self) -> bool {
+        !self
+            .name
+            .chars()
+            .any(|c| c == '"' || c == '\\' || c.is_control())
+    }
+
     /// Gets the deck name visible to the player in game
     ///
     /// # Example
@@ -114,6 +477,28 @@ impl DeckList {
         self.name.as_str()
     }
 
+    /// Set the deck name only if it is currently empty, returning whether it changed
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    ///
+    /// assert!(list.set_name_if_empty("Imported"));
+    /// assert!(!list.set_name_if_empty("Ignored"));
+    /// assert_eq!(list.name(), "Imported");
+    /// ```
+    pub fn set_name_if_empty(&mut self, name: impl Into<String>) -> bool {
+        if !self.name.is_empty() {
+            return false;
+        }
+
+        self.name = name.into();
+        true
+    }
+
     /// Set the list of cards.
     ///  
     /// # Example
@@ -133,6 +518,7 @@ impl DeckList {
             .iter()
             .map(|name| Card {
                 name: name.to_string(),
+                meta: None,
             })
             .collect();
         self.cards = list;
@@ -156,81 +542,3375 @@ impl DeckList {
         self.cards.iter().map(|card| card.name.clone()).collect()
     }
 
-    /// Convert a string copied from Marvel Snap into a DeckList.
+    /// Get list of cards as a boxed slice, for caches that keep card lists for long.
     ///
-    /// # Panics
+    /// Unlike [`DeckList::cards`], the result cannot grow and holds no spare capacity.
     ///
-    /// Panics if the code cannot be resolved into a valid DeckList struct.
-    pub fn from_code<T: AsRef<[u8]>>(code: T) -> Result<Self, DeckListError> {
-        let value = general_purpose::STANDARD_NO_PAD
-            .decode(code)
-            .map_err(DeckListError::DecodingError)?;
-
-        let json: DeckList = serde_json::from_slice(value.as_slice())
-            .map_err(|_| DeckListError::InvalidDeckInput)?;
-
-        Ok(json)
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan", "Thanos"]);
+    ///
+    /// let cards: Box<[String]> = list.cards_boxed();
+    ///
+    /// assert_eq!(&*cards, ["AntMan", "Thanos"]);
+    /// ```
+    pub fn cards_boxed(&self) -> Box<[String]> {
+        self.cards.iter().map(|card| card.name.clone()).collect()
     }
 
-    /// Converts DeckList into a string for pasting into Marvel Snap
-    ///
-    /// For a complete deck, make sure to set both the deck name and include 12 valid cards.
-    /// For simplicity, this library does not validate if the cards exist in the game.
+    /// Set the list of cards from existing [`Card`] values
     ///
     /// # Example
+    ///
     /// ```rust
     /// use marvelsnapdeck::DeckList;
     ///
+    /// let mut source = DeckList::new();
+    /// source.set_cards(&["AntMan"]);
+    ///
     /// let mut list = DeckList::new();
-    /// list.set_name("Thanos".to_string());
-    /// list.set_cards(&["AntMan", "Agent13", "Quinjet", "Angela",
-    /// "Okoye", "Armor", "Falcon", "Mystique", "Lockjaw",
-    /// "KaZar", "DevilDinosaur", "Thanos"]);
-    /// let code = list.into_code().unwrap();
+    /// list.set_cards_from_cards(source.into_cards());
+    ///
+    /// assert_eq!(list.cards(), vec!["AntMan"]);
     /// ```
+    pub fn set_cards_from_cards(&mut self, cards: Vec<Card>) {
+        self.cards = cards;
+    }
+
+    /// Consume the DeckList into its [`Card`] values without cloning
     ///
-    /// # Panics
+    /// # Example
     ///
-    /// Panics if the underlying card list fails to encode as a string
-    pub fn into_code(&self) -> Result<String, DeckListError> {
-        let data = serde_json::to_string(self).map_err(|_| DeckListError::EncodingError)?;
-
-        let code = general_purpose::STANDARD_NO_PAD.encode(data);
-
-        Ok(code)
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan", "Thanos"]);
+    ///
+    /// let cards = list.into_cards();
+    ///
+    /// assert_eq!(cards.len(), 2);
+    /// assert_eq!(cards[1].name(), "Thanos");
+    /// ```
+    pub fn into_cards(self) -> Vec<Card> {
+        self.cards
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::DeckList;
+    /// Iterate over card names without cloning them
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan", "Thanos"]);
+    ///
+    /// assert_eq!(list.iter_cards().last(), Some("Thanos"));
+    /// ```
+    pub fn iter_cards(&self) -> impl Iterator<Item = &str> + '_ {
+        self.cards.iter().map(|card| card.name.as_str())
+    }
 
-    const VALID_CODE: &'static str = "eyJOYW1lIjoiVGhhbm9zIiwiQ2FyZHMiOlt7IkNhcmREZWZJZCI6IkFudE1hbiJ9LHsiQ2FyZERlZklkIjoiQWdlbnQxMyJ9LHsiQ2FyZERlZklkIjoiUXVpbmpldCJ9LHsiQ2FyZERlZklkIjoiQW5nZWxhIn0seyJDYXJkRGVmSWQiOiJPa295ZSJ9LHsiQ2FyZERlZklkIjoiQXJtb3IifSx7IkNhcmREZWZJZCI6IkZhbGNvbiJ9LHsiQ2FyZERlZklkIjoiTXlzdGlxdWUifSx7IkNhcmREZWZJZCI6IkxvY2tqYXcifSx7IkNhcmREZWZJZCI6IkthWmFyIn0seyJDYXJkRGVmSWQiOiJEZXZpbERpbm9zYXVyIn0seyJDYXJkRGVmSWQiOiJUaGFub3MifV19";
+    /// Iterate over card names along with their slot index
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan", "Thanos"]);
+    ///
+    /// let slots: Vec<(usize, &str)> = list.enumerate_cards().collect();
+    ///
+    /// assert_eq!(slots, vec![(0, "AntMan"), (1, "Thanos")]);
+    /// ```
+    pub fn enumerate_cards(&self) -> impl Iterator<Item = (usize, &str)> + '_ {
+        self.iter_cards().enumerate()
+    }
 
-    #[test]
-    fn decode_is_valid() {
-        let list = DeckList::from_code(&VALID_CODE.to_string()).unwrap();
-        assert_eq!(list.name(), "Thanos");
-        assert_eq!(list.cards.len(), 12);
+    /// Get the card at a slot in the deck, if there is one
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan", "Thanos"]);
+    ///
+    /// assert_eq!(list.get_card(1), Some("Thanos"));
+    /// assert_eq!(list.get_card(2), None);
+    /// ```
+    pub fn get_card(&self, index: usize) -> Option<&str> {
+        self.cards.get(index).map(|card| card.name.as_str())
     }
 
-    #[test]
-    fn decode_cards() {
-        let list = DeckList::from_code(&VALID_CODE.to_string()).unwrap();
-        let cards = list.cards();
+    /// Map each card to its slot index, for repeated lookups without scanning the deck.
+    ///
+    /// A card that appears more than once maps to its first slot.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan", "Thanos", "AntMan"]);
+    ///
+    /// let index = list.index_map();
+    /// assert_eq!(index["AntMan"], 0);
+    /// assert_eq!(index["Thanos"], 1);
+    /// ```
+    pub fn index_map(&self) -> HashMap<String, usize> {
+        let mut index = HashMap::with_capacity(self.cards.len());
+        for (slot, name) in self.enumerate_cards() {
+            index.entry(name.to_string()).or_insert(slot);
+        }
+        index
+    }
 
-        assert_eq!(cards.len(), 12);
-        assert_eq!(cards[0], "AntMan");
+    /// Get the card names sorted alphabetically, without reordering the deck
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["Thanos", "AntMan"]);
+    ///
+    /// assert_eq!(list.sorted_cards(), vec!["AntMan", "Thanos"]);
+    /// ```
+    pub fn sorted_cards(&self) -> Vec<String> {
+        let mut cards = self.cards();
+        cards.sort();
+        cards
     }
 
-    #[test]
-    fn encode_cards() {
-        let mut list = DeckList::new();
-        list.set_name("Thanos".to_string());
-        list.set_cards(&[
-            "AntMan",
-            "Agent13",
-            "Quinjet",
+    /// A key for sorting or grouping decks by content, not meant for display.
+    ///
+    /// The key is the sorted card names joined by commas, so decks with the same cards
+    /// in any order have the same key. The deck name is ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut first = DeckList::new();
+    /// first.set_cards(&["Thanos", "AntMan"]);
+    /// let mut second = DeckList::new();
+    /// second.set_cards(&["Lockjaw"]);
+    ///
+    /// let mut decks = vec![first, second];
+    /// decks.sort_by_key(DeckList::sort_key);
+    ///
+    /// assert_eq!(decks[0].sort_key(), "AntMan,Thanos");
+    /// ```
+    pub fn sort_key(&self) -> String {
+        self.sorted_cards().join(",")
+    }
+
+    /// Get the card names sorted by a key, highest first, without reordering the deck.
+    ///
+    /// Cards with the same key keep their order from the deck. This works for any
+    /// caller provided ordering such as popularity or cost.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan", "Thanos", "Lockjaw"]);
+    /// let popularity = HashMap::from([("Thanos", 90), ("Lockjaw", 40)]);
+    ///
+    /// let sorted = list.cards_sorted_by(|name| *popularity.get(name).unwrap_or(&0));
+    ///
+    /// assert_eq!(sorted, vec!["Thanos", "Lockjaw", "AntMan"]);
+    /// ```
+    pub fn cards_sorted_by<F: Fn(&str) -> i64>(&self, key: F) -> Vec<String> {
+        let mut cards = self.cards();
+        cards.sort_by_key(|name| std::cmp::Reverse(key(name)));
+        cards
+    }
+
+    /// Group card names by their uppercase first letter, for A to Z sections.
+    ///
+    /// Cards keep their deck order within a group, and empty card names are grouped
+    /// under `#`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan", "Thanos", "Angela"]);
+    ///
+    /// let groups = list.group_by_initial();
+    ///
+    /// assert_eq!(groups[&'A'], vec!["AntMan", "Angela"]);
+    /// assert_eq!(groups[&'T'], vec!["Thanos"]);
+    /// ```
+    pub fn group_by_initial(&self) -> BTreeMap<char, Vec<String>> {
+        let mut groups: BTreeMap<char, Vec<String>> = BTreeMap::new();
+        for card in &self.cards {
+            let initial = card
+                .name
+                .chars()
+                .next()
+                .and_then(|c| c.to_uppercase().next())
+                .unwrap_or('#');
+            groups.entry(initial).or_default().push(card.name.clone());
+        }
+        groups
+    }
+
+    /// Sort the cards by name, keeping duplicates in their existing order
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["Thanos", "AntMan"]);
+    /// list.sort_cards();
+    ///
+    /// assert_eq!(list.cards(), vec!["AntMan", "Thanos"]);
+    /// ```
+    pub fn sort_cards(&mut self) {
+        self.cards.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+
+    /// Sort the cards by name without preserving the order of duplicates.
+    ///
+    /// This is faster than [`DeckList::sort_cards`] for large card pools where the
+    /// relative order of equal names does not matter.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["Thanos", "AntMan"]);
+    /// list.sort_cards_unstable();
+    ///
+    /// assert_eq!(list.cards(), vec!["AntMan", "Thanos"]);
+    /// ```
+    pub fn sort_cards_unstable(&mut self) {
+        self.cards.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+    }
+
+    /// Sort cards by name whenever the deck is encoded, leaving the stored order untouched.
+    ///
+    /// This is off by default so codes keep the order the cards were added in.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["Thanos", "AntMan"]);
+    /// list.set_canonical_ordering(true);
+    ///
+    /// let decoded = DeckList::from_code(list.into_code().unwrap()).unwrap();
+    ///
+    /// assert_eq!(decoded.cards(), vec!["AntMan", "Thanos"]);
+    /// assert_eq!(list.cards(), vec!["Thanos", "AntMan"]);
+    /// ```
+    pub fn set_canonical_ordering(&mut self, enabled: bool) {
+        self.canonical_ordering = enabled;
+    }
+
+    /// Check if cards are sorted by name when the deck is encoded
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_canonical_ordering(true);
+    ///
+    /// assert!(list.canonical_ordering());
+    /// ```
+    pub fn canonical_ordering(&self) -> bool {
+        self.canonical_ordering
+    }
+
+    /// Record which tool created or imported the deck.
+    ///
+    /// The tag is never part of the code from [`DeckList::into_code`], so the deck can
+    /// be shared as usual. Use [`DeckList::into_tagged_json`] to store it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// let code = list.into_code().unwrap();
+    /// list.set_source_tag("my-tool");
+    ///
+    /// assert_eq!(list.source_tag(), Some("my-tool"));
+    /// assert_eq!(list.into_code().unwrap(), code);
+    /// ```
+    pub fn set_source_tag(&mut self, tag: impl Into<String>) {
+        self.source_tag = Some(tag.into());
+    }
+
+    /// Gets the tag set by [`DeckList::set_source_tag`], if any
+    pub fn source_tag(&self) -> Option<&str> {
+        self.source_tag.as_deref()
+    }
+
+    /// Store extra JSON fields for [`DeckList::into_code_with_extra`] to add to the code.
+    ///
+    /// This lets tools carry forward game fields this crate does not model. Decoding
+    /// does not capture unknown fields, so read them from the code's JSON yourself and
+    /// apply them here. Fields are merged into any applied before, and `Name` and
+    /// `Cards` are ignored so they cannot override the deck itself.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    /// use serde_json::json;
+    ///
+    /// let mut list = DeckList::new();
+    /// let extra = json!({"Version": 2});
+    /// list.apply_extra(extra.as_object().unwrap());
+    ///
+    /// let code = list.into_code_with_extra().unwrap();
+    /// assert_eq!(DeckList::from_code(&code).unwrap().cards().len(), 0);
+    /// ```
+    pub fn apply_extra(&mut self, extra: &serde_json::Map<String, serde_json::Value>) {
+        for (key, value) in extra {
+            if key != "Name" && key != "Cards" {
+                self.extra.insert(key.clone(), value.clone());
+            }
+        }
+    }
+
+    /// Consume the DeckList into its name and card names.
+    ///
+    /// These are plain owned strings, which makes them a convenient starting point for
+    /// FFI layers that should not depend on the serde types.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_name("Thanos".into());
+    /// list.set_cards(&["AntMan"]);
+    ///
+    /// let (name, cards) = list.name_and_cards_owned();
+    ///
+    /// assert_eq!(name, "Thanos");
+    /// assert_eq!(cards, vec!["AntMan".to_string()]);
+    /// ```
+    pub fn name_and_cards_owned(self) -> (String, Vec<String>) {
+        let cards = self.cards.into_iter().map(|card| card.name).collect();
+        (self.name, cards)
+    }
+
+    /// Borrow the deck name and cards together without allocating
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_name("Thanos".into());
+    /// list.set_cards(&["AntMan", "Thanos"]);
+    ///
+    /// let (name, cards) = list.parts();
+    /// let lines: Vec<String> = cards
+    ///     .iter()
+    ///     .map(|card| format!("{name}: {}", card.name()))
+    ///     .collect();
+    ///
+    /// assert_eq!(lines, vec!["Thanos: AntMan", "Thanos: Thanos"]);
+    /// ```
+    pub fn parts(&self) -> (&str, &[Card]) {
+        (&self.name, &self.cards)
+    }
+
+    /// Number of card slots left to fill before the deck reaches [`DECK_SIZE`].
+    ///
+    /// Duplicate cards only fill a single slot, and full or overfull decks return zero.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan", "Thanos", "Thanos"]);
+    ///
+    /// assert_eq!(list.remaining_slots(), 10);
+    /// ```
+    pub fn remaining_slots(&self) -> usize {
+        DECK_SIZE.saturating_sub(self.card_set().len())
+    }
+
+    /// Keep only the first `max` cards, dropping the rest.
+    ///
+    /// Passing [`DECK_SIZE`] clips an overfull import down to a legal card count.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::{DeckList, DECK_SIZE};
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&vec!["AntMan"; 14]);
+    /// list.truncate_cards(DECK_SIZE);
+    ///
+    /// assert_eq!(list.cards().len(), DECK_SIZE);
+    /// ```
+    pub fn truncate_cards(&mut self, max: usize) {
+        self.cards.truncate(max);
+    }
+
+    /// Check the deck is complete and would be accepted by the game.
+    ///
+    /// Every problem found is returned, a valid deck has a non-empty name without
+    /// control characters and exactly [`DECK_SIZE`] different cards. Whether the cards
+    /// exist in the game is not checked.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan"]);
+    ///
+    /// let errors = list.validate().unwrap_err();
+    ///
+    /// assert_eq!(errors.len(), 2);
+    /// ```
+    pub fn validate(&self) -> Result<(), Vec<DeckListError>> {
+        let mut errors = Vec::new();
+
+        if self.name.is_empty() {
+            errors.push(DeckListError::EmptyName);
+        } else if !self.name_is_valid() {
+            errors.push(DeckListError::InvalidName);
+        }
+
+        if self.cards.len() != DECK_SIZE {
+            errors.push(DeckListError::WrongCardCount(self.cards.len()));
+        }
+
+        let mut seen = HashSet::new();
+        let mut reported = HashSet::new();
+        for card in &self.cards {
+            if !seen.insert(card.name.as_str()) && reported.insert(card.name.as_str()) {
+                errors.push(DeckListError::DuplicateCard(card.name.clone()));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Fix what can be fixed automatically, then [`DeckList::validate`] the result.
+    ///
+    /// The name is trimmed of surrounding whitespace, card names are converted with
+    /// [`to_def_id`] and repeated cards are removed, keeping the first copy. Problems
+    /// which need a decision from the player are returned, such as an empty name, a
+    /// name with control characters or the wrong number of cards.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::{DeckList, DeckListError};
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_name("  Thanos ".into());
+    /// list.set_cards(&["Ant-Man", "AntMan", "Thanos"]);
+    ///
+    /// let errors = list.sanitize().unwrap_err();
+    ///
+    /// assert_eq!(list.name(), "Thanos");
+    /// assert_eq!(list.cards(), vec!["AntMan", "Thanos"]);
+    /// assert!(matches!(errors[..], [DeckListError::WrongCardCount(2)]));
+    /// ```
+    pub fn sanitize(&mut self) -> Result<(), Vec<DeckListError>> {
+        self.name = self.name.trim().to_string();
+        self.normalize_card_names();
+
+        let mut seen = HashSet::new();
+        self.cards.retain(|card| seen.insert(card.name.clone()));
+
+        self.validate()
+    }
+
+    /// Check if the deck passes [`DeckList::validate`] and can be used in game
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_name("Thanos".into());
+    /// list.set_cards(&["AntMan", "Agent13", "Quinjet", "Angela",
+    /// "Okoye", "Armor", "Falcon", "Mystique", "Lockjaw",
+    /// "KaZar", "DevilDinosaur", "Thanos"]);
+    ///
+    /// assert!(list.is_game_legal());
+    /// ```
+    pub fn is_game_legal(&self) -> bool {
+        self.validate().is_ok()
+    }
+
+    /// Check the deck is [`DeckList::is_game_legal`] and every card is legal in a format.
+    ///
+    /// The legal cards come from the caller, such as the card pool of a rotating format.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::collections::HashSet;
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let cards = ["AntMan", "Agent13", "Quinjet", "Angela", "Okoye", "Armor",
+    /// "Falcon", "Mystique", "Lockjaw", "KaZar", "DevilDinosaur", "Thanos"];
+    /// let mut list = DeckList::new();
+    /// list.set_name("Thanos".into());
+    /// list.set_cards(&cards);
+    ///
+    /// let mut legal: HashSet<String> = cards.iter().map(|name| name.to_string()).collect();
+    /// assert!(list.is_legal_in(&legal));
+    ///
+    /// legal.remove("Thanos");
+    /// assert!(!list.is_legal_in(&legal));
+    /// ```
+    pub fn is_legal_in(&self, legal_cards: &HashSet<String>) -> bool {
+        self.is_game_legal()
+            && self
+                .cards
+                .iter()
+                .all(|card| legal_cards.contains(&card.name))
+    }
+
+    /// Check if any card appears more than once, stopping at the first repeat
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan", "Thanos", "AntMan"]);
+    ///
+    /// assert!(list.has_duplicates());
+    /// ```
+    pub fn has_duplicates(&self) -> bool {
+        let mut seen = HashSet::with_capacity(self.cards.len());
+        !self
+            .cards
+            .iter()
+            .all(|card| seen.insert(card.name.as_str()))
+    }
+
+    /// Count the cards in the deck, both in total and without duplicates
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan", "Thanos", "Thanos"]);
+    ///
+    /// assert_eq!(list.counts(), (3, 2));
+    /// ```
+    pub fn counts(&self) -> (usize, usize) {
+        (self.cards.len(), self.card_set().len())
+    }
+
+    /// Check if every card of an archetype signature is in the deck
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan", "Thanos", "Lockjaw"]);
+    ///
+    /// assert!(list.matches_signature(&["Thanos", "Lockjaw"]));
+    /// assert!(!list.matches_signature(&["Thanos", "Nebula"]));
+    /// ```
+    pub fn matches_signature(&self, signature: &[&str]) -> bool {
+        signature
+            .iter()
+            .all(|required| self.cards.iter().any(|card| card.name == *required))
+    }
+
+    /// Name the deck after the first archetype whose signature cards are all present.
+    ///
+    /// Signatures are pairs of an archetype name and its required cards. If none
+    /// match, the name is left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan", "Thanos", "Lockjaw"]);
+    /// list.auto_name(&[
+    ///     ("Destroy", &["Deadpool", "Carnage"]),
+    ///     ("Thanos", &["Thanos", "Lockjaw"]),
+    /// ]);
+    ///
+    /// assert_eq!(list.name(), "Thanos");
+    /// ```
+    pub fn auto_name(&mut self, signatures: &[(&str, &[&str])]) {
+        if let Some((name, _)) = signatures
+            .iter()
+            .find(|(_, signature)| self.matches_signature(signature))
+        {
+            self.name = name.to_string();
+        }
+    }
+
+    /// Find the first archetype keyword that appears in the deck name, ignoring case
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_name("thanos lockjaw v2".into());
+    ///
+    /// assert_eq!(list.name_archetype(&["Zoo", "Thanos"]), Some("Thanos".to_string()));
+    /// assert_eq!(list.name_archetype(&["Zoo"]), None);
+    /// ```
+    pub fn name_archetype(&self, keywords: &[&str]) -> Option<String> {
+        let name = self.name.to_lowercase();
+        keywords
+            .iter()
+            .find(|keyword| name.contains(&keyword.to_lowercase()))
+            .map(|keyword| keyword.to_string())
+    }
+
+    /// Get the set of unique card names in the deck
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan", "Thanos", "Thanos"]);
+    ///
+    /// let set = list.card_set();
+    ///
+    /// assert_eq!(set.len(), 2);
+    /// assert!(set.contains("Thanos"));
+    /// ```
+    pub fn card_set(&self) -> HashSet<&str> {
+        self.cards.iter().map(|card| card.name.as_str()).collect()
+    }
+
+    /// Check if two decks have at least `min_shared` cards in common
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut first = DeckList::new();
+    /// first.set_cards(&["AntMan", "Thanos", "Lockjaw"]);
+    /// let mut second = DeckList::new();
+    /// second.set_cards(&["Thanos", "Lockjaw", "Nebula"]);
+    ///
+    /// assert!(first.shares_core(&second, 2));
+    /// assert!(!first.shares_core(&second, 3));
+    /// ```
+    pub fn shares_core(&self, other: &DeckList, min_shared: usize) -> bool {
+        self.intersection_count(other) >= min_shared
+    }
+
+    /// Count the unique cards both decks have in common
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut first = DeckList::new();
+    /// first.set_cards(&["AntMan", "Thanos", "Lockjaw"]);
+    /// let mut second = DeckList::new();
+    /// second.set_cards(&["Thanos", "Lockjaw", "Nebula"]);
+    ///
+    /// assert_eq!(first.intersection_count(&second), 2);
+    /// ```
+    pub fn intersection_count(&self, other: &DeckList) -> usize {
+        let theirs = other.card_set();
+        self.card_set()
+            .into_iter()
+            .filter(|name| theirs.contains(name))
+            .count()
+    }
+
+    /// Count how many of the deck's unique cards are in a collection of owned cards
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::collections::HashSet;
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan", "Thanos", "Lockjaw"]);
+    /// let owned: HashSet<String> = ["AntMan", "Lockjaw", "Nebula"]
+    ///     .iter()
+    ///     .map(|name| name.to_string())
+    ///     .collect();
+    ///
+    /// assert_eq!(list.owned_count(&owned), 2);
+    /// ```
+    pub fn owned_count(&self, owned: &HashSet<String>) -> usize {
+        self.card_set()
+            .into_iter()
+            .filter(|name| owned.contains(*name))
+            .count()
+    }
+
+    /// A copy of the deck with only the cards in a collection of owned cards.
+    ///
+    /// Card order and the name are kept, so the result may be an incomplete deck.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::collections::HashSet;
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan", "Thanos", "Lockjaw"]);
+    /// let owned: HashSet<String> = ["Lockjaw", "AntMan"].iter().map(|name| name.to_string()).collect();
+    ///
+    /// assert_eq!(list.restrict_to_owned(&owned).cards(), vec!["AntMan", "Lockjaw"]);
+    /// ```
+    pub fn restrict_to_owned(&self, owned: &HashSet<String>) -> DeckList {
+        let mut list = self.clone();
+        list.cards.retain(|card| owned.contains(&card.name));
+        list
+    }
+
+    /// List the unusual cards whose popularity is below a threshold, sorted by name.
+    ///
+    /// Cards missing from the popularity map count as zero, the most off-meta.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["Thanos", "Squirrel", "AntMan"]);
+    /// let popularity = HashMap::from([("Thanos".to_string(), 0.6), ("AntMan".to_string(), 0.02)]);
+    ///
+    /// assert_eq!(list.off_meta_cards(&popularity, 0.1), vec!["AntMan", "Squirrel"]);
+    /// ```
+    pub fn off_meta_cards(&self, popularity: &HashMap<String, f32>, threshold: f32) -> Vec<String> {
+        let mut cards: Vec<String> = self
+            .card_set()
+            .into_iter()
+            .filter(|name| popularity.get(*name).copied().unwrap_or(0.0) < threshold)
+            .map(str::to_string)
+            .collect();
+        cards.sort();
+        cards
+    }
+
+    /// Get the card names sorted by a release index, newest first.
+    ///
+    /// Higher indexes are newer. Cards missing from the release order sort last, keeping
+    /// their deck order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan", "Custom", "Thanos"]);
+    /// let release_order = HashMap::from([("AntMan".to_string(), 1), ("Thanos".to_string(), 80)]);
+    ///
+    /// assert_eq!(list.newest_cards(&release_order), vec!["Thanos", "AntMan", "Custom"]);
+    /// ```
+    pub fn newest_cards(&self, release_order: &HashMap<String, u32>) -> Vec<String> {
+        let mut cards = self.cards();
+        cards.sort_by_key(|name| std::cmp::Reverse(release_order.get(name).copied()));
+        cards
+    }
+
+    /// Count the cards to add and remove to turn this deck into another.
+    ///
+    /// This is the size of the symmetric difference of both card sets, so it ignores
+    /// the deck names, card order and duplicates.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut first = DeckList::new();
+    /// first.set_cards(&["AntMan", "Nebula"]);
+    /// let mut second = DeckList::new();
+    /// second.set_cards(&["AntMan", "Thanos"]);
+    ///
+    /// assert_eq!(first.edit_distance(&second), 2);
+    /// ```
+    pub fn edit_distance(&self, other: &DeckList) -> usize {
+        self.card_set()
+            .symmetric_difference(&other.card_set())
+            .count()
+    }
+
+    /// Guess whether cards use display names like `Ant-Man` instead of ids like `AntMan`.
+    ///
+    /// This is a heuristic which looks for spaces or hyphens in card names, which card
+    /// definition ids never contain. A true result suggests the cards need normalizing
+    /// before the game will accept the deck.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["Ant-Man", "Devil Dinosaur"]);
+    ///
+    /// assert!(list.uses_display_names());
+    /// ```
+    pub fn uses_display_names(&self) -> bool {
+        self.cards
+            .iter()
+            .any(|card| card.name.contains(|c: char| c.is_whitespace() || c == '-'))
+    }
+
+    /// Check every card name is ASCII, as card definition ids always are.
+    ///
+    /// Non-ASCII names usually come from corrupted data or display name imports.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan", "Thanós"]);
+    ///
+    /// assert!(!list.cards_are_ascii());
+    /// ```
+    pub fn cards_are_ascii(&self) -> bool {
+        self.cards.iter().all(|card| card.name.is_ascii())
+    }
+
+    /// List the cards whose name is not in the definition id format, `[A-Za-z0-9]+`.
+    ///
+    /// Names with spaces or punctuation are likely display names that need
+    /// [`DeckList::normalize_card_names`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan", "Ant-Man", "Agent13", "Devil Dinosaur"]);
+    ///
+    /// assert_eq!(list.cards_match_format(), vec!["Ant-Man", "Devil Dinosaur"]);
+    /// ```
+    pub fn cards_match_format(&self) -> Vec<String> {
+        self.iter_cards()
+            .filter(|name| name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric()))
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Check for blank slots, cards whose name is empty or only whitespace
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan", "Thanos"]);
+    ///
+    /// assert!(!list.has_empty_cards());
+    /// ```
+    pub fn has_empty_cards(&self) -> bool {
+        self.cards.iter().any(|card| card.name.trim().is_empty())
+    }
+
+    /// Convert every card name with [`to_def_id`], such as `Ant-Man` into `AntMan`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["Ant-Man", "Devil Dinosaur"]);
+    /// list.normalize_card_names();
+    ///
+    /// assert_eq!(list.cards(), vec!["AntMan", "DevilDinosaur"]);
+    /// ```
+    pub fn normalize_card_names(&mut self) {
+        for card in &mut self.cards {
+            card.name = to_def_id(&card.name);
+        }
+    }
+
+    /// Normalize card names like [`DeckList::normalize_card_names`], returning the originals.
+    ///
+    /// The map goes from each normalized name to the name it was converted from. If
+    /// several names normalize to the same id, the first one in the deck is kept.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["Ant-Man"]);
+    ///
+    /// let originals = list.normalize_card_names_tracked();
+    ///
+    /// assert_eq!(list.cards(), vec!["AntMan"]);
+    /// assert_eq!(originals["AntMan"], "Ant-Man");
+    /// ```
+    pub fn normalize_card_names_tracked(&mut self) -> HashMap<String, String> {
+        let mut originals = HashMap::new();
+        for card in &mut self.cards {
+            let normalized = to_def_id(&card.name);
+            let original = std::mem::replace(&mut card.name, normalized.clone());
+            originals.entry(normalized).or_insert(original);
+        }
+        originals
+    }
+
+    /// Check if two decks hold the same cards, ignoring their names and card order
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut first = DeckList::new();
+    /// first.set_name("Thanos".into());
+    /// first.set_cards(&["AntMan", "Thanos"]);
+    /// let mut second = DeckList::new();
+    /// second.set_cards(&["Thanos", "AntMan"]);
+    ///
+    /// assert!(first.same_cards(&second));
+    /// ```
+    pub fn same_cards(&self, other: &DeckList) -> bool {
+        if self.cards.len() != other.cards.len() {
+            return false;
+        }
+
+        let mut ours: Vec<&str> = self.cards.iter().map(|card| card.name.as_str()).collect();
+        let mut theirs: Vec<&str> = other.cards.iter().map(|card| card.name.as_str()).collect();
+        ours.sort_unstable();
+        theirs.sort_unstable();
+
+        ours == theirs
+    }
+
+    /// Compute a stable hash of the cards in the deck.
+    ///
+    /// The fingerprint ignores the name and card order, so decks with the same cards
+    /// share a fingerprint. It uses FNV-1a, which stays the same across platforms and
+    /// releases, making it safe to store.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut first = DeckList::new();
+    /// first.set_name("Thanos".into());
+    /// first.set_cards(&["AntMan", "Thanos"]);
+    /// let mut second = DeckList::new();
+    /// second.set_cards(&["Thanos", "AntMan"]);
+    ///
+    /// assert_eq!(first.fingerprint(), second.fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const PRIME: u64 = 0x0100_0000_01b3;
+
+        let mut names: Vec<&str> = self.cards.iter().map(|card| card.name.as_str()).collect();
+        names.sort_unstable();
+
+        names.iter().fold(OFFSET_BASIS, |hash, name| {
+            name.bytes()
+                .chain(std::iter::once(0))
+                .fold(hash, |hash, byte| {
+                    (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+                })
+        })
+    }
+
+    /// Get a stable seed for picking a deck's color or generated icon.
+    ///
+    /// The seed comes from the [`DeckList::fingerprint`], so decks with the same cards
+    /// get the same seed whatever their name. It is only meant for presentation.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// const PALETTE: [&str; 4] = ["red", "blue", "green", "purple"];
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan", "Thanos"]);
+    ///
+    /// let mut copy = list.clone_with_name("Thanos v2");
+    /// copy.set_cards(&["Thanos", "AntMan"]);
+    ///
+    /// let color = |list: &DeckList| PALETTE[list.color_seed() as usize % PALETTE.len()];
+    ///
+    /// assert_eq!(copy.color_seed(), list.color_seed());
+    /// assert_eq!(color(&copy), color(&list));
+    /// ```
+    pub fn color_seed(&self) -> u32 {
+        let fingerprint = self.fingerprint();
+        (fingerprint >> 32) as u32 ^ fingerprint as u32
+    }
+
+    /// Get a short id for the cards in the deck, suitable for URLs.
+    ///
+    /// This is 8 base32 characters taken from the [`DeckList::fingerprint`], so decks
+    /// with the same cards share an id. It only identifies a deck, the cards cannot be
+    /// recovered from it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan", "Thanos"]);
+    ///
+    /// assert_eq!(list.short_id().len(), 8);
+    /// ```
+    pub fn short_id(&self) -> String {
+        const ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+        let fingerprint = self.fingerprint();
+        (0..8)
+            .map(|i| ALPHABET[((fingerprint >> (59 - i * 5)) & 0x1f) as usize] as char)
+            .collect()
+    }
+
+    /// Compare against another deck, listing the cards needed to turn this deck into it.
+    ///
+    /// Cards are listed in the order they appear in their deck.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut before = DeckList::new();
+    /// before.set_cards(&["AntMan", "Nebula"]);
+    /// let mut after = DeckList::new();
+    /// after.set_cards(&["AntMan", "Thanos"]);
+    ///
+    /// let diff = before.diff(&after);
+    ///
+    /// assert_eq!(diff.added, vec!["Thanos".to_string()]);
+    /// assert_eq!(diff.removed, vec!["Nebula".to_string()]);
+    /// ```
+    pub fn diff(&self, other: &DeckList) -> DeckDiff {
+        let ours = self.card_set();
+        let theirs = other.card_set();
+
+        DeckDiff {
+            added: other
+                .cards
+                .iter()
+                .filter(|card| !ours.contains(card.name.as_str()))
+                .map(|card| card.name.clone())
+                .collect(),
+            removed: self
+                .cards
+                .iter()
+                .filter(|card| !theirs.contains(card.name.as_str()))
+                .map(|card| card.name.clone())
+                .collect(),
+        }
+    }
+
+    /// Every deck one card away from this one, for exploring tweaks to a list.
+    ///
+    /// Each slot is replaced in turn by each candidate not already in the deck, keeping
+    /// the name. That is one deck per slot for every new candidate, so 12 slots and 50
+    /// candidates give 600 decks.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan", "Thanos"]);
+    ///
+    /// let swaps = list.single_swaps(&["Thanos", "Lockjaw"]);
+    ///
+    /// assert_eq!(swaps.len(), 2);
+    /// assert_eq!(swaps[0].cards(), vec!["Lockjaw", "Thanos"]);
+    /// assert_eq!(swaps[1].cards(), vec!["AntMan", "Lockjaw"]);
+    /// ```
+    pub fn single_swaps(&self, candidates: &[&str]) -> Vec<DeckList> {
+        let present = self.card_set();
+        let candidates: Vec<&str> = candidates
+            .iter()
+            .copied()
+            .filter(|candidate| !present.contains(candidate))
+            .collect();
+
+        let mut swaps = Vec::with_capacity(self.cards.len() * candidates.len());
+        for slot in 0..self.cards.len() {
+            for candidate in &candidates {
+                let mut swap = self.clone();
+                swap.cards[slot] = Card {
+                    name: candidate.to_string(),
+                    meta: None,
+                };
+                swaps.push(swap);
+            }
+        }
+        swaps
+    }
+
+    /// A copy of the deck's shape with the contents hidden, such as for deck puzzles.
+    ///
+    /// The name is cleared and each card is replaced by its position, `Card1` to `Card12`
+    /// for a complete deck. The real cards cannot be recovered from the result.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_name("Thanos".into());
+    /// list.set_cards(&["AntMan", "Thanos"]);
+    ///
+    /// let redacted = list.redacted();
+    ///
+    /// assert_eq!(redacted.name(), "");
+    /// assert_eq!(redacted.cards(), vec!["Card1", "Card2"]);
+    /// ```
+    pub fn redacted(&self) -> DeckList {
+        let mut list = DeckList::new();
+        list.cards = (1..=self.cards.len())
+            .map(|slot| Card {
+                name: format!("Card{slot}"),
+                meta: None,
+            })
+            .collect();
+        list
+    }
+
+    /// A deck named `diff` holding the cards that are in exactly one of the two decks.
+    ///
+    /// Cards only in this deck come first, then cards only in the other, each in deck
+    /// order. This is meant for showing changes with deck widgets, the result is not a
+    /// legal deck.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut before = DeckList::new();
+    /// before.set_cards(&["AntMan", "Nebula"]);
+    /// let mut after = DeckList::new();
+    /// after.set_cards(&["AntMan", "Thanos"]);
+    ///
+    /// let changes = before.symmetric_difference(&after);
+    ///
+    /// assert_eq!(changes.name(), "diff");
+    /// assert_eq!(changes.cards(), vec!["Nebula", "Thanos"]);
+    /// ```
+    pub fn symmetric_difference(&self, other: &DeckList) -> DeckList {
+        let DeckDiff { added, removed } = self.diff(other);
+
+        let mut list = DeckList::new();
+        list.set_name("diff".into());
+        list.set_cards(&[removed, added].concat());
+        list
+    }
+
+    /// The cards that tell two decks apart, as (only in this deck, only in the other).
+    ///
+    /// Both lists are sorted and hold each card once, ready for side by side display.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut first = DeckList::new();
+    /// first.set_cards(&["Thanos", "Nebula", "AntMan", "Lockjaw"]);
+    /// let mut second = DeckList::new();
+    /// second.set_cards(&["Thanos", "Quinjet", "AntMan", "Agent13"]);
+    ///
+    /// let (ours, theirs) = first.distinguishing_cards(&second);
+    ///
+    /// assert_eq!(ours, vec!["Lockjaw", "Nebula"]);
+    /// assert_eq!(theirs, vec!["Agent13", "Quinjet"]);
+    /// ```
+    pub fn distinguishing_cards(&self, other: &DeckList) -> (Vec<String>, Vec<String>) {
+        let ours = self.card_set();
+        let theirs = other.card_set();
+        let only = |a: &HashSet<&str>, b: &HashSet<&str>| {
+            let mut cards: Vec<String> = a.difference(b).map(|name| name.to_string()).collect();
+            cards.sort();
+            cards
+        };
+
+        (only(&ours, &theirs), only(&theirs, &ours))
+    }
+
+    /// Summarize what changed since an earlier version of this deck
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut previous = DeckList::new();
+    /// previous.set_name("Thanos".into());
+    /// previous.set_cards(&["AntMan", "Nebula"]);
+    /// let mut current = previous.clone();
+    /// current.set_cards(&["AntMan", "Thanos", "Lockjaw"]);
+    ///
+    /// let summary = current.change_summary(&previous);
+    ///
+    /// assert!(!summary.name_changed);
+    /// assert_eq!(summary.added, vec!["Lockjaw", "Thanos"]);
+    /// assert_eq!(summary.removed, vec!["Nebula"]);
+    /// assert_eq!(summary.net_card_delta, 1);
+    /// ```
+    pub fn change_summary(&self, previous: &DeckList) -> ChangeSummary {
+        let DeckDiff {
+            mut added,
+            mut removed,
+        } = previous.diff(self);
+        added.sort();
+        removed.sort();
+
+        ChangeSummary {
+            name_changed: self.name != previous.name,
+            added,
+            removed,
+            net_card_delta: self.cards.len() as isize - previous.cards.len() as isize,
+        }
+    }
+
+    /// A one line preview of the deck for chat, showing its first three cards.
+    ///
+    /// See [`DeckList::summary_with`] to show a different number of cards.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_name("Thanos".into());
+    /// list.set_cards(&["AntMan", "Agent13", "Quinjet", "Angela", "Thanos"]);
+    ///
+    /// assert_eq!(list.summary(), "Thanos — 5 cards: AntMan, Agent13, Quinjet, … (+2)");
+    /// ```
+    pub fn summary(&self) -> String {
+        self.summary_with(3)
+    }
+
+    /// A one line preview of the deck, showing at most `max_shown` cards.
+    ///
+    /// The name is left out when empty, and the hidden cards are counted at the end.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan", "Thanos"]);
+    ///
+    /// assert_eq!(list.summary_with(5), "2 cards: AntMan, Thanos");
+    /// assert_eq!(list.summary_with(1), "2 cards: AntMan, … (+1)");
+    /// ```
+    pub fn summary_with(&self, max_shown: usize) -> String {
+        let mut summary = String::new();
+        if !self.name.is_empty() {
+            summary.push_str(&self.name);
+            summary.push_str(" — ");
+        }
+
+        let count = self.cards.len();
+        summary.push_str(&format!(
+            "{count} {}",
+            if count == 1 { "card" } else { "cards" }
+        ));
+        if count == 0 {
+            return summary;
+        }
+
+        let mut shown: Vec<&str> = self.iter_cards().take(max_shown).collect();
+        let hidden = format!("… (+{})", count - shown.len());
+        if shown.len() < count {
+            shown.push(&hidden);
+        }
+        summary.push_str(": ");
+        summary.push_str(&shown.join(", "));
+        summary
+    }
+
+    /// Describe the [`DeckList::diff`] to another deck as printable lines.
+    ///
+    /// Added cards are listed first as `+ Card`, followed by removed cards as `- Card`,
+    /// one per line.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut before = DeckList::new();
+    /// before.set_cards(&["AntMan", "Nebula"]);
+    /// let mut after = DeckList::new();
+    /// after.set_cards(&["AntMan", "Thanos"]);
+    ///
+    /// assert_eq!(before.diff_text(&after), "+ Thanos\n- Nebula");
+    /// ```
+    pub fn diff_text(&self, other: &DeckList) -> String {
+        let diff = self.diff(other);
+        let added = diff.added.iter().map(|name| format!("+ {}", name));
+        let removed = diff.removed.iter().map(|name| format!("- {}", name));
+
+        added.chain(removed).collect::<Vec<_>>().join("\n")
+    }
+
+    /// Convert a string copied from Marvel Snap into a DeckList.
+    ///
+    /// Empty or whitespace only input returns [`DeckListError::InvalidDeckInput`].
+    /// Any of the [`KNOWN_PREFIXES`] added by other tools are removed before decoding.
+    /// A JSON array holding exactly one deck is also accepted, while arrays with more
+    /// decks return [`DeckListError::MultipleDecks`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the code cannot be resolved into a valid DeckList struct.
+    pub fn from_code<T: AsRef<[u8]>>(code: T) -> Result<Self, DeckListError> {
+        let code = strip_prefixes_bytes(code.as_ref(), KNOWN_PREFIXES);
+        Self::from_code_with(code, &general_purpose::STANDARD_NO_PAD)
+    }
+
+    /// Convert a code into a DeckList, tolerating common formatting differences.
+    ///
+    /// Before decoding, all whitespace is removed along with any [`KNOWN_PREFIXES`]
+    /// and trailing `=` padding, and the URL-safe characters `-` and `_` are mapped back
+    /// to `+` and `/`. This accepts codes that went through chat apps, links or other
+    /// tools, which the stricter [`DeckList::from_code`] may reject.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_name("Thanos".into());
+    /// let code = list.into_code().unwrap();
+    ///
+    /// let messy = format!(" {}\n{}== ", &code[..10], &code[10..]);
+    ///
+    /// assert_eq!(DeckList::from_code_lenient(&messy).unwrap(), list);
+    /// ```
+    pub fn from_code_lenient(code: &str) -> Result<Self, DeckListError> {
+        let compact: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+        let cleaned: String = strip_known_prefixes(&compact)
+            .trim_end_matches('=')
+            .chars()
+            .map(|c| match c {
+                '-' => '+',
+                '_' => '/',
+                c => c,
+            })
+            .collect();
+
+        Self::from_code(cleaned)
+    }
+
+    /// Convert a payload written by [`DeckList::to_qr_payload`] into a DeckList.
+    ///
+    /// The [`QR_SCHEME`] is removed if present and the rest is read with
+    /// [`DeckList::from_code_lenient`], so plain codes scanned from QR codes work too.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_name("Thanos".into());
+    /// let payload = list.to_qr_payload().unwrap();
+    ///
+    /// assert_eq!(DeckList::from_qr_payload(&payload).unwrap(), list);
+    /// ```
+    pub fn from_qr_payload(payload: &str) -> Result<Self, DeckListError> {
+        let payload = payload.trim();
+        Self::from_code_lenient(payload.strip_prefix(QR_SCHEME).unwrap_or(payload))
+    }
+
+    /// Convert a code taken from a link, where it may be percent-encoded, into a DeckList.
+    ///
+    /// Sequences such as `%2B` and `%2F`, which appear for the `+` and `/` characters of
+    /// standard base64, are decoded before calling [`DeckList::from_code`]. Malformed
+    /// sequences return [`DeckListError::InvalidDeckInput`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_name("Zoo???".into());
+    /// let code = list.into_code().unwrap();
+    /// let component = code.replace('/', "%2F");
+    ///
+    /// assert_eq!(DeckList::from_url_component(&component).unwrap(), list);
+    /// ```
+    pub fn from_url_component(s: &str) -> Result<Self, DeckListError> {
+        let bytes = s.as_bytes();
+        let mut decoded = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+
+        while i < bytes.len() {
+            if bytes[i] == b'%' {
+                let hex = bytes
+                    .get(i + 1..i + 3)
+                    .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+                    .and_then(|hex| std::str::from_utf8(hex).ok())
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .ok_or(DeckListError::InvalidDeckInput)?;
+                decoded.push(hex);
+                i += 3;
+            } else {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+
+        Self::from_code(decoded)
+    }
+
+    /// Convert a code into a DeckList, refusing input longer than `max_bytes`.
+    ///
+    /// The length is checked before any decoding, which makes this the safer choice
+    /// for untrusted input such as a server endpoint. A complete deck is usually well
+    /// under a kilobyte, so a limit of 4096 bytes leaves plenty of room for long names.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::{DeckList, DeckListError};
+    ///
+    /// let code = DeckList::new().into_code().unwrap();
+    ///
+    /// assert!(DeckList::from_code_limited(&code, 4096).is_ok());
+    /// assert!(matches!(
+    ///     DeckList::from_code_limited(&code, 8),
+    ///     Err(DeckListError::InputTooLarge)
+    /// ));
+    /// ```
+    pub fn from_code_limited<T: AsRef<[u8]>>(
+        code: T,
+        max_bytes: usize,
+    ) -> Result<Self, DeckListError> {
+        if code.as_ref().len() > max_bytes {
+            return Err(DeckListError::InputTooLarge);
+        }
+
+        Self::from_code(code)
+    }
+
+    /// Convert a code into a DeckList using a custom base64 engine.
+    ///
+    /// The game only produces and accepts codes using the standard alphabet without
+    /// padding, which is what [`DeckList::from_code`] uses. Other engines are useful
+    /// for codes produced by other tools, such as URL-safe variants.
+    ///
+    /// Unlike [`DeckList::from_code`], no prefix is removed before decoding, so codes
+    /// which may carry one of the [`KNOWN_PREFIXES`] must be passed through
+    /// [`strip_known_prefixes`] or [`strip_prefixes`] first.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use base64::engine::general_purpose::URL_SAFE;
+    /// use marvelsnapdeck::{strip_known_prefixes, DeckList};
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_name("Thanos".into());
+    /// let code = format!("MSD:{}", list.into_code_with(&URL_SAFE).unwrap());
+    ///
+    /// assert!(DeckList::from_code_with(&code, &URL_SAFE).is_err());
+    ///
+    /// let decoded = DeckList::from_code_with(strip_known_prefixes(&code), &URL_SAFE).unwrap();
+    /// assert_eq!(decoded.name(), "Thanos");
+    /// ```
+    pub fn from_code_with<T: AsRef<[u8]>, E: Engine>(
+        code: T,
+        engine: &E,
+    ) -> Result<Self, DeckListError> {
+        match decode_json(code.as_ref(), engine)? {
+            Payload::One(list) => Ok(list),
+            Payload::Many(mut lists) if lists.len() == 1 => Ok(lists.remove(0)),
+            Payload::Many(lists) if lists.is_empty() => Err(DeckListError::InvalidDeckInput),
+            Payload::Many(_) => Err(DeckListError::MultipleDecks),
+        }
+    }
+
+    /// Convert pasted text containing a code into a DeckList.
+    ///
+    /// The first code found by [`extract_codes`] is decoded. If a line starts with
+    /// `Name:`, the rest of that line replaces the decoded deck name unless it is
+    /// blank. Markdown
+    /// backticks around either line are ignored. Text without a code returns
+    /// [`DeckListError::InvalidDeckInput`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let code = DeckList::new().into_code().unwrap();
+    /// let text = format!("Name: Thanos\n{}\n", code);
+    ///
+    /// let list = DeckList::from_clipboard(&text).unwrap();
+    ///
+    /// assert_eq!(list.name(), "Thanos");
+    /// ```
+    pub fn from_clipboard(text: &str) -> Result<Self, DeckListError> {
+        let code = extract_codes(text)
+            .into_iter()
+            .next()
+            .ok_or(DeckListError::InvalidDeckInput)?;
+        let mut list = Self::from_code(code)?;
+
+        let name = text
+            .lines()
+            .find_map(|line| line.trim().trim_matches('`').strip_prefix("Name:"))
+            .map(|name| name.trim().trim_end_matches('`').trim())
+            .filter(|name| !name.is_empty());
+
+        if let Some(name) = name {
+            list.name = name.to_string();
+        }
+
+        Ok(list)
+    }
+
+    /// Convert a code holding several decks into a list of DeckList.
+    ///
+    /// The JSON inside the code may either be an array of decks, as produced by tools
+    /// that batch export, or a single deck object which becomes a one element list.
+    /// Like [`DeckList::from_code`], any of the [`KNOWN_PREFIXES`] are removed first.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use base64::{engine::general_purpose::STANDARD_NO_PAD, Engine};
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let code = STANDARD_NO_PAD.encode(
+    ///     r#"[{"Name":"Thanos","Cards":[]},{"Name":"Destroy","Cards":[]}]"#,
+    /// );
+    /// let lists = DeckList::many_from_code(code).unwrap();
+    ///
+    /// assert_eq!(lists.len(), 2);
+    /// assert_eq!(lists[1].name(), "Destroy");
+    /// ```
+    pub fn many_from_code<T: AsRef<[u8]>>(code: T) -> Result<Vec<DeckList>, DeckListError> {
+        let code = strip_prefixes_bytes(code.as_ref(), KNOWN_PREFIXES);
+
+        match decode_json(code, &general_purpose::STANDARD_NO_PAD)? {
+            Payload::One(list) => Ok(vec![list]),
+            Payload::Many(lists) => Ok(lists),
+        }
+    }
+
+    /// Parse a deck from the flat JSON schema used by many community tools.
+    ///
+    /// This schema lists cards as plain strings, `{"name": "Thanos", "cards": ["AntMan"]}`,
+    /// instead of the game's `CardDefId` objects. A missing name is left empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let list = DeckList::from_flat_json(r#"{"name": "Thanos", "cards": ["AntMan"]}"#).unwrap();
+    ///
+    /// assert_eq!(list.name(), "Thanos");
+    /// assert_eq!(list.cards(), vec!["AntMan"]);
+    /// ```
+    pub fn from_flat_json(json: &str) -> Result<Self, DeckListError> {
+        let flat: FlatDeck =
+            serde_json::from_str(json).map_err(|_| DeckListError::InvalidDeckInput)?;
+
+        let mut list = Self::new();
+        list.name = flat.name;
+        list.set_cards(&flat.cards);
+        Ok(list)
+    }
+
+    /// Parse a deck from the JSON exported by third-party deck trackers.
+    ///
+    /// Trackers use `{"deckName": "Thanos", "cards": [{"cardDefId": "AntMan", "count": 1}]}`,
+    /// where `deckName` becomes the deck name and each `cardDefId` is added `count` times.
+    /// A missing name is left empty and a missing count is read as one. Since a deck
+    /// holds one copy of each card, a count above one fails with
+    /// [`DeckListError::CardCountTooHigh`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let json = r#"{"deckName": "Thanos", "cards": [{"cardDefId": "AntMan", "count": 1}]}"#;
+    /// let list = DeckList::from_tracker_json(json).unwrap();
+    ///
+    /// assert_eq!(list.name(), "Thanos");
+    /// assert_eq!(list.cards(), vec!["AntMan"]);
+    /// ```
+    pub fn from_tracker_json(json: &str) -> Result<Self, DeckListError> {
+        let tracker: TrackerDeck =
+            serde_json::from_str(json).map_err(|_| DeckListError::InvalidDeckInput)?;
+
+        let mut list = Self::new();
+        list.name = tracker.name;
+        for card in tracker.cards {
+            if card.count > 1 {
+                return Err(DeckListError::CardCountTooHigh(card.name, card.count));
+            }
+            list.cards.extend(std::iter::repeat_n(
+                Card {
+                    name: card.name,
+                    meta: None,
+                },
+                card.count,
+            ));
+        }
+        Ok(list)
+    }
+
+    /// Converts the cards into a bitset with one bit per card of a fixed registry.
+    ///
+    /// Bit `i` is set when `registry[i]` is in the deck, counting from the lowest bit of
+    /// the first byte. This is very compact for storing many decks with a known card
+    /// pool, but it holds neither the name, the card order nor duplicates. Cards missing
+    /// from the registry fail with [`DeckListError::UnknownCard`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let registry = ["AntMan", "Lockjaw", "Thanos"];
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["Thanos", "AntMan"]);
+    ///
+    /// let bits = list.to_bitset(&registry).unwrap();
+    ///
+    /// assert_eq!(bits, vec![0b101]);
+    /// assert_eq!(DeckList::from_bitset(&bits, &registry).unwrap().cards(), vec!["AntMan", "Thanos"]);
+    /// ```
+    pub fn to_bitset(&self, registry: &[&str]) -> Result<Vec<u8>, DeckListError> {
+        let mut bits = vec![0; registry.len().div_ceil(8)];
+        for name in self.iter_cards() {
+            let index = registry
+                .iter()
+                .position(|card| *card == name)
+                .ok_or_else(|| DeckListError::UnknownCard(name.to_string()))?;
+            bits[index / 8] |= 1 << (index % 8);
+        }
+        Ok(bits)
+    }
+
+    /// Read the cards back from a [`DeckList::to_bitset`] bitset, in registry order.
+    ///
+    /// Bits set past the end of the registry fail with [`DeckListError::InvalidDeckInput`].
+    pub fn from_bitset(bits: &[u8], registry: &[&str]) -> Result<Self, DeckListError> {
+        let mut list = Self::new();
+        for (index, _) in bits
+            .iter()
+            .flat_map(|byte| (0..8).map(move |bit| byte & (1 << bit) != 0))
+            .enumerate()
+            .filter(|(_, set)| *set)
+        {
+            let name = registry.get(index).ok_or(DeckListError::InvalidDeckInput)?;
+            list.cards.push(Card {
+                name: name.to_string(),
+                meta: None,
+            });
+        }
+        Ok(list)
+    }
+
+    /// Converts DeckList into a JSON value in the game's format
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_name("Thanos".into());
+    ///
+    /// let value = list.to_value().unwrap();
+    ///
+    /// assert_eq!(value["Name"], "Thanos");
+    /// ```
+    pub fn to_value(&self) -> Result<serde_json::Value, DeckListError> {
+        serde_json::to_value(&*self.for_encoding()).map_err(|_| DeckListError::EncodingError)
+    }
+
+    /// Convert a JSON value in the game's format into a DeckList
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    /// use serde_json::json;
+    ///
+    /// let value = json!({"Name": "Thanos", "Cards": [{"CardDefId": "AntMan"}]});
+    /// let list = DeckList::from_value(value).unwrap();
+    ///
+    /// assert_eq!(list.cards(), vec!["AntMan"]);
+    /// ```
+    pub fn from_value(value: serde_json::Value) -> Result<Self, DeckListError> {
+        serde_json::from_value(value).map_err(|_| DeckListError::InvalidDeckInput)
+    }
+
+    /// Converts DeckList into JSON for tool storage, including the source tag.
+    ///
+    /// This is the game's JSON with an extra `SourceTag` field when a tag is set. It is
+    /// meant for storage, use [`DeckList::into_code`] for sharing.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_source_tag("my-tool");
+    ///
+    /// assert_eq!(
+    ///     list.into_tagged_json().unwrap(),
+    ///     r#"{"Name":"","Cards":[],"SourceTag":"my-tool"}"#
+    /// );
+    /// ```
+    pub fn into_tagged_json(&self) -> Result<String, DeckListError> {
+        let tagged = TaggedDeck {
+            deck: &self.for_encoding(),
+            source_tag: self.source_tag.as_deref(),
+        };
+
+        serde_json::to_string(&tagged).map_err(|_| DeckListError::EncodingError)
+    }
+
+    /// Convert a JSON value in either the game's format or the flat format into a DeckList.
+    ///
+    /// The name is read from `Name` or `name`, and may be missing. Cards are read from
+    /// `Cards` or `cards`, where each element is inspected: strings are used as card
+    /// ids directly, as in [`DeckList::from_flat_json`], while objects must hold a
+    /// `CardDefId` string like the game's format.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    /// use serde_json::json;
+    ///
+    /// let game = json!({"Name": "Thanos", "Cards": [{"CardDefId": "AntMan"}]});
+    /// let flat = json!({"name": "Thanos", "cards": ["AntMan"]});
+    ///
+    /// assert_eq!(
+    ///     DeckList::from_value_lenient(&game).unwrap(),
+    ///     DeckList::from_value_lenient(&flat).unwrap()
+    /// );
+    /// ```
+    pub fn from_value_lenient(value: &serde_json::Value) -> Result<Self, DeckListError> {
+        use serde_json::Value;
+
+        let object = value.as_object().ok_or(DeckListError::InvalidDeckInput)?;
+
+        let name = match object.get("Name").or_else(|| object.get("name")) {
+            Some(name) => name.as_str().ok_or(DeckListError::InvalidDeckInput)?,
+            None => "",
+        };
+
+        let cards = object
+            .get("Cards")
+            .or_else(|| object.get("cards"))
+            .and_then(Value::as_array)
+            .ok_or(DeckListError::InvalidDeckInput)?
+            .iter()
+            .map(|card| match card {
+                Value::String(name) => Ok(name.as_str()),
+                Value::Object(card) => card
+                    .get("CardDefId")
+                    .and_then(Value::as_str)
+                    .ok_or(DeckListError::InvalidDeckInput),
+                _ => Err(DeckListError::InvalidDeckInput),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut list = Self::new();
+        list.name = name.to_string();
+        list.set_cards(&cards);
+        Ok(list)
+    }
+
+    /// Converts DeckList into the smallest JSON describing it.
+    ///
+    /// This matches the game's JSON, except the `Name` field is left out when the name
+    /// is empty. It has not been confirmed that the game accepts codes without a name,
+    /// so use [`DeckList::to_json_bytes`] for anything meant to be pasted in game.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan"]);
+    ///
+    /// assert_eq!(list.to_minimal_json().unwrap(), r#"{"Cards":[{"CardDefId":"AntMan"}]}"#);
+    /// ```
+    pub fn to_minimal_json(&self) -> Result<String, DeckListError> {
+        let deck = self.for_encoding();
+        let minimal = MinimalDeck {
+            name: &deck.name,
+            cards: &deck.cards,
+        };
+
+        serde_json::to_string(&minimal).map_err(|_| DeckListError::EncodingError)
+    }
+
+    /// Converts DeckList into CSV with a `deck,card` header and one row per card.
+    ///
+    /// Fields containing commas, quotes or line breaks are quoted, doubling any quotes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_name("Thanos".into());
+    /// list.set_cards(&["AntMan", "Thanos"]);
+    ///
+    /// assert_eq!(list.to_csv(), "deck,card\nThanos,AntMan\nThanos,Thanos\n");
+    /// ```
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(CSV_HEADER);
+        self.write_csv_rows(&mut csv);
+        csv
+    }
+
+    fn write_csv_rows(&self, csv: &mut String) {
+        let name = csv_field(&self.name);
+        for card in &self.cards {
+            csv.push_str(&name);
+            csv.push(',');
+            csv.push_str(&csv_field(&card.name));
+            csv.push('\n');
+        }
+    }
+
+    /// Converts DeckList into the flat JSON schema read by [`DeckList::from_flat_json`]
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_name("Thanos".into());
+    /// list.set_cards(&["AntMan"]);
+    ///
+    /// assert_eq!(list.to_flat_json().unwrap(), r#"{"name":"Thanos","cards":["AntMan"]}"#);
+    /// ```
+    pub fn to_flat_json(&self) -> Result<String, DeckListError> {
+        let deck = self.for_encoding();
+        let flat = FlatDeck {
+            name: deck.name.clone(),
+            cards: deck.cards(),
+        };
+
+        serde_json::to_string(&flat).map_err(|_| DeckListError::EncodingError)
+    }
+
+    /// Converts DeckList into a string for pasting into Marvel Snap
+    ///
+    /// For a complete deck, make sure to set both the deck name and include 12 valid cards.
+    /// For simplicity, this library does not validate if the cards exist in the game.
+    ///
+    /// # Example
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_name("Thanos".to_string());
+    /// list.set_cards(&["AntMan", "Agent13", "Quinjet", "Angela",
+    /// "Okoye", "Armor", "Falcon", "Mystique", "Lockjaw",
+    /// "KaZar", "DevilDinosaur", "Thanos"]);
+    /// let code = list.into_code().unwrap();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the underlying card list fails to encode as a string
+    pub fn into_code(&self) -> Result<String, DeckListError> {
+        self.into_code_with(&general_purpose::STANDARD_NO_PAD)
+    }
+
+    /// Converts DeckList into a compressed code for storing many decks.
+    ///
+    /// The JSON is compressed with raw DEFLATE before being base64 encoded, which makes
+    /// codes roughly half as long. This is a format for tools only, the game cannot
+    /// read these codes. Use [`DeckList::from_code_compressed`] to read them back.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_name("Thanos".to_string());
+    /// list.set_cards(&["AntMan", "Agent13", "Quinjet", "Angela",
+    /// "Okoye", "Armor", "Falcon", "Mystique", "Lockjaw",
+    /// "KaZar", "DevilDinosaur", "Thanos"]);
+    ///
+    /// let compressed = list.into_code_compressed().unwrap();
+    ///
+    /// assert!(compressed.len() < list.into_code().unwrap().len());
+    /// assert_eq!(DeckList::from_code_compressed(compressed).unwrap(), list);
+    /// ```
+    #[cfg(feature = "compress")]
+    pub fn into_code_compressed(&self) -> Result<String, DeckListError> {
+        use flate2::{write::DeflateEncoder, Compression};
+        use std::io::Write;
+
+        let data = self.to_json_bytes()?;
+
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+        encoder
+            .write_all(&data)
+            .map_err(|_| DeckListError::EncodingError)?;
+        let compressed = encoder.finish().map_err(|_| DeckListError::EncodingError)?;
+
+        let code = general_purpose::STANDARD_NO_PAD.encode(compressed);
+
+        Ok(code)
+    }
+
+    /// Convert a code written by [`DeckList::into_code_compressed`] into a DeckList.
+    ///
+    /// Codes from tools that gzip the JSON instead of using raw DEFLATE are detected by
+    /// their magic bytes and read too. These codes are not produced by the game, use
+    /// [`DeckList::from_code`] for those.
+    #[cfg(feature = "compress")]
+    pub fn from_code_compressed<T: AsRef<[u8]>>(code: T) -> Result<Self, DeckListError> {
+        let value = general_purpose::STANDARD_NO_PAD
+            .decode(code)
+            .map_err(DeckListError::DecodingError)?;
+
+        let json = if value.starts_with(&[0x1f, 0x8b]) {
+            read_decompressed(flate2::read::GzDecoder::new(value.as_slice()))?
+        } else {
+            read_decompressed(flate2::read::DeflateDecoder::new(value.as_slice()))?
+        };
+
+        serde_json::from_slice(&json).map_err(|_| DeckListError::InvalidDeckInput)
+    }
+
+    /// Converts DeckList into a [`DeckCode`] for APIs that only accept valid codes
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let list = DeckList::new();
+    /// let code = list.into_deck_code().unwrap();
+    ///
+    /// assert_eq!(code.as_str(), list.into_code().unwrap());
+    /// ```
+    pub fn into_deck_code(&self) -> Result<DeckCode, DeckListError> {
+        Ok(DeckCode(self.into_code()?))
+    }
+
+    /// Converts DeckList into a code with the cards sorted by name.
+    ///
+    /// Decks with the same name and cards always produce the same canonical code,
+    /// whatever order the cards were added in.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut first = DeckList::new();
+    /// first.set_cards(&["Thanos", "AntMan"]);
+    /// let mut second = DeckList::new();
+    /// second.set_cards(&["AntMan", "Thanos"]);
+    ///
+    /// assert_eq!(first.canonical_code().unwrap(), second.canonical_code().unwrap());
+    /// ```
+    pub fn canonical_code(&self) -> Result<String, DeckListError> {
+        let mut sorted = self.clone();
+        sorted.sort_cards();
+        sorted.into_code()
+    }
+
+    /// The bytes of [`DeckList::canonical_code`], ready to use as a cache or dedup key
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["Thanos", "AntMan"]);
+    ///
+    /// assert_eq!(
+    ///     list.canonical_code_bytes().unwrap(),
+    ///     list.canonical_code().unwrap().as_bytes()
+    /// );
+    /// ```
+    pub fn canonical_code_bytes(&self) -> Result<Vec<u8>, DeckListError> {
+        self.canonical_code().map(String::into_bytes)
+    }
+
+    /// Compare the code length of the standard, canonical and nameless encodings
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_name("Thanos".into());
+    /// list.set_cards(&["Thanos", "AntMan"]);
+    ///
+    /// let report = list.code_size_report().unwrap();
+    ///
+    /// assert_eq!(report.standard, list.into_code().unwrap().len());
+    /// assert_eq!(report.canonical, report.standard);
+    /// assert!(report.nameless < report.standard);
+    /// ```
+    pub fn code_size_report(&self) -> Result<CodeSizeReport, DeckListError> {
+        let nameless = MinimalDeck {
+            name: "",
+            cards: &self.for_encoding().cards,
+        };
+        let nameless = serde_json::to_vec(&nameless).map_err(|_| DeckListError::EncodingError)?;
+
+        Ok(CodeSizeReport {
+            standard: self.into_code()?.len(),
+            canonical: self.canonical_code()?.len(),
+            nameless: general_purpose::STANDARD_NO_PAD.encode(nameless).len(),
+        })
+    }
+
+    /// Converts DeckList into a code, or an empty string if encoding failed.
+    ///
+    /// Encoding a DeckList should never fail in practice, this is meant for display
+    /// code such as templates that cannot handle a `Result`. An empty string always
+    /// means [`DeckList::into_code`] returned an error.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let list = DeckList::new();
+    ///
+    /// assert_eq!(list.to_code_or_empty(), list.into_code().unwrap());
+    /// ```
+    pub fn to_code_or_empty(&self) -> String {
+        self.into_code().unwrap_or_default()
+    }
+
+    /// Converts DeckList into the payload to encode in a QR code for sharing.
+    ///
+    /// The payload is [`QR_SCHEME`], `msd://`, followed by the code in the URL-safe base64
+    /// alphabet, so it survives scanners that open it as a link. Rendering the QR code
+    /// is left to a QR library.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_name("Thanos".into());
+    /// let payload = list.to_qr_payload().unwrap();
+    ///
+    /// let code = payload.strip_prefix("msd://").unwrap();
+    ///
+    /// assert!(code.starts_with("eyJOYW1l"));
+    /// assert!(!code.contains(['+', '/', '=']));
+    /// ```
+    pub fn to_qr_payload(&self) -> Result<String, DeckListError> {
+        let code = self.into_code_with(&general_purpose::URL_SAFE_NO_PAD)?;
+        Ok(format!("{QR_SCHEME}{code}"))
+    }
+
+    /// Converts DeckList into a string using a custom base64 engine.
+    ///
+    /// Marvel Snap expects the standard alphabet without padding, so codes produced
+    /// with any other engine will likely not be accepted by the game. Use
+    /// [`DeckList::into_code`] for codes meant to be pasted in game.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use base64::engine::general_purpose::STANDARD;
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let list = DeckList::new();
+    /// let code = list.into_code_with(&STANDARD).unwrap();
+    ///
+    /// assert!(code.ends_with('='));
+    /// ```
+    pub fn into_code_with<E: Engine>(&self, engine: &E) -> Result<String, DeckListError> {
+        let data = self.to_json_bytes()?;
+
+        let code = engine.encode(data);
+
+        Ok(code)
+    }
+
+    /// Converts DeckList into a code with the fields from [`DeckList::apply_extra`] added.
+    ///
+    /// The extra fields follow `Name` and `Cards` in the JSON, sorted by key. Objects
+    /// nested inside an extra field are written as serde_json stores them.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use base64::{engine::general_purpose, Engine};
+    /// use marvelsnapdeck::DeckList;
+    /// use serde_json::json;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_name("Thanos".into());
+    /// list.apply_extra(json!({"Version": 2}).as_object().unwrap());
+    ///
+    /// let code = list.into_code_with_extra().unwrap();
+    /// let json = general_purpose::STANDARD_NO_PAD.decode(code).unwrap();
+    ///
+    /// assert_eq!(json, br#"{"Name":"Thanos","Cards":[],"Version":2}"#);
+    /// ```
+    pub fn into_code_with_extra(&self) -> Result<String, DeckListError> {
+        let data = serde_json::to_vec(&ExtraDeck {
+            deck: &self.for_encoding(),
+            extra: self
+                .extra
+                .iter()
+                .map(|(key, value)| (key.as_str(), value))
+                .collect(),
+        })
+        .map_err(|_| DeckListError::EncodingError)?;
+
+        Ok(general_purpose::STANDARD_NO_PAD.encode(data))
+    }
+
+    /// Converts DeckList into the JSON bytes the game base64 encodes into a code.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let list = DeckList::new();
+    /// let bytes = list.to_json_bytes().unwrap();
+    ///
+    /// assert_eq!(bytes, br#"{"Name":"","Cards":[]}"#);
+    /// ```
+    pub fn to_json_bytes(&self) -> Result<Vec<u8>, DeckListError> {
+        serde_json::to_vec(&*self.for_encoding()).map_err(|_| DeckListError::EncodingError)
+    }
+
+    /// The deck as every encoder writes it, with the cards sorted under canonical ordering
+    fn for_encoding(&self) -> std::borrow::Cow<'_, DeckList> {
+        if self.canonical_ordering {
+            let mut sorted = self.clone();
+            sorted.sort_cards();
+            std::borrow::Cow::Owned(sorted)
+        } else {
+            std::borrow::Cow::Borrowed(self)
+        }
+    }
+}
+
+/// Decks are equal when their name and cards match, the settings and data kept out of
+/// codes are ignored.
+impl PartialEq for DeckList {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.cards == other.cards
+    }
+}
+
+/// Decodes a code with [`DeckList::from_code`]
+///
+/// # Example
+///
+/// ```rust
+/// use marvelsnapdeck::{DeckList, DeckListError};
+///
+/// # fn main() -> Result<(), DeckListError> {
+/// let code = "eyJOYW1lIjoiVGhhbm9zIiwiQ2FyZHMiOlt7IkNhcmREZWZJZCI6IkFudE1hbiJ9XX0";
+/// let list: DeckList = code.try_into()?;
+///
+/// assert_eq!(list.name(), "Thanos");
+/// # Ok(())
+/// # }
+/// ```
+impl TryFrom<&str> for DeckList {
+    type Error = DeckListError;
+
+    fn try_from(code: &str) -> Result<Self, Self::Error> {
+        DeckList::from_code(code)
+    }
+}
+
+/// Decodes a code with [`DeckList::from_code`]
+///
+/// # Example
+///
+/// ```rust
+/// use marvelsnapdeck::DeckList;
+///
+/// let code = String::from("eyJOYW1lIjoiVGhhbm9zIiwiQ2FyZHMiOlt7IkNhcmREZWZJZCI6IkFudE1hbiJ9XX0");
+/// let list = DeckList::try_from(code).unwrap();
+///
+/// assert_eq!(list.cards(), vec!["AntMan"]);
+/// ```
+impl TryFrom<String> for DeckList {
+    type Error = DeckListError;
+
+    fn try_from(code: String) -> Result<Self, Self::Error> {
+        DeckList::from_code(&code)
+    }
+}
+
+/// Encodes a deck with [`DeckList::into_code`]
+///
+/// Fails with [`DeckListError::EncodingError`] if the deck cannot be serialized to JSON.
+///
+/// # Example
+///
+/// ```rust
+/// use marvelsnapdeck::{DeckList, DeckListError};
+///
+/// # fn main() -> Result<(), DeckListError> {
+/// let mut list = DeckList::new();
+/// list.set_name("Thanos".into());
+///
+/// let code: String = (&list).try_into()?;
+/// assert_eq!(DeckList::from_code(&code)?, list);
+/// # Ok(())
+/// # }
+/// ```
+impl TryFrom<&DeckList> for String {
+    type Error = DeckListError;
+
+    fn try_from(list: &DeckList) -> Result<Self, Self::Error> {
+        list.into_code()
+    }
+}
+
+/// Decompressed output is capped to guard against decompression bombs
+#[cfg(feature = "compress")]
+const MAX_DECOMPRESSED_LEN: u64 = 1 << 20;
+
+/// Read all of a decompressing reader, failing on corrupt data or oversized output
+#[cfg(feature = "compress")]
+fn read_decompressed<R: std::io::Read>(reader: R) -> Result<Vec<u8>, DeckListError> {
+    use std::io::Read;
+
+    let mut output = Vec::new();
+    reader
+        .take(MAX_DECOMPRESSED_LEN + 1)
+        .read_to_end(&mut output)
+        .map_err(|_| DeckListError::DecompressionError)?;
+
+    if output.len() as u64 > MAX_DECOMPRESSED_LEN {
+        return Err(DeckListError::DecompressionError);
+    }
+    Ok(output)
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.chars().any(char::is_control)
+}
+
+const CSV_HEADER: &str = "deck,card\n";
+
+/// Quote a CSV field if it contains separators, quotes or line breaks
+fn csv_field(field: &str) -> std::borrow::Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\"")).into()
+    } else {
+        field.into()
+    }
+}
+
+/// Decode base64 and parse the JSON inside into any of the code payloads
+fn decode_json<T: DeserializeOwned, E: Engine>(
+    code: &[u8],
+    engine: &E,
+) -> Result<T, DeckListError> {
+    if code.iter().all(u8::is_ascii_whitespace) {
+        return Err(DeckListError::InvalidDeckInput);
+    }
+
+    let value = engine.decode(code).map_err(DeckListError::DecodingError)?;
+
+    serde_json::from_slice(value.as_slice()).map_err(|_| DeckListError::InvalidDeckInput)
+}
+
+impl Card {
+    /// Gets the card definition id, such as `AntMan`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckList;
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan"]);
+    ///
+    /// let cards = list.into_cards();
+    ///
+    /// assert_eq!(cards[0].name(), "AntMan");
+    /// ```
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Attach metadata to the card.
+    ///
+    /// Metadata only lives in memory, it is dropped when the deck is encoded so codes
+    /// stay identical to the ones the game produces.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::{CardMeta, DeckList};
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan"]);
+    /// let code = list.into_code().unwrap();
+    ///
+    /// let mut cards = list.into_cards();
+    /// cards[0].set_meta(CardMeta {
+    ///     favorite: true,
+    ///     notes: "Great early drop".into(),
+    /// });
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards_from_cards(cards);
+    ///
+    /// assert_eq!(list.into_code().unwrap(), code);
+    /// ```
+    pub fn set_meta(&mut self, meta: CardMeta) {
+        self.meta = Some(Box::new(meta));
+    }
+
+    /// Gets the metadata attached to the card, if any
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::{CardMeta, DeckList};
+    ///
+    /// let mut list = DeckList::new();
+    /// list.set_cards(&["AntMan"]);
+    ///
+    /// let mut cards = list.into_cards();
+    /// assert!(cards[0].meta().is_none());
+    ///
+    /// cards[0].set_meta(CardMeta {
+    ///     favorite: true,
+    ///     ..Default::default()
+    /// });
+    /// assert!(cards[0].meta().unwrap().favorite);
+    /// ```
+    pub fn meta(&self) -> Option<&CardMeta> {
+        self.meta.as_deref()
+    }
+}
+
+/// Cards are equal when their definition ids match, metadata is ignored
+impl PartialEq for Card {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+/// A share code which is known to decode into a [`DeckList`]
+///
+/// # Example
+///
+/// ```rust
+/// use marvelsnapdeck::{DeckCode, DeckList};
+///
+/// let mut list = DeckList::new();
+/// list.set_name("Thanos".into());
+///
+/// let code = DeckCode::new(list.into_code().unwrap()).unwrap();
+///
+/// assert_eq!(code.decode().unwrap().name(), "Thanos");
+/// assert!(DeckCode::new("not a code").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DeckCode(String);
+
+impl DeckCode {
+    /// Wrap a code after checking that it decodes.
+    ///
+    /// Surrounding whitespace and any of the [`KNOWN_PREFIXES`] are removed, so the
+    /// stored code is the one the game accepts.
+    pub fn new(code: impl Into<String>) -> Result<Self, DeckListError> {
+        let code = code.into();
+        let code = strip_known_prefixes(code.trim());
+        DeckList::from_code(code)?;
+        Ok(Self(code.to_string()))
+    }
+
+    /// Decode the code into a DeckList
+    pub fn decode(&self) -> Result<DeckList, DeckListError> {
+        DeckList::from_code(&self.0)
+    }
+
+    /// Gets the code as a string
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl std::fmt::Display for DeckCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Compare decks by their cards alone, ignoring card order and the deck name.
+///
+/// Duplicates still count, so this compares the card lists as multisets with
+/// [`DeckList::same_cards`]. Equality on [`DeckList`] itself stays strict.
+///
+/// # Example
+///
+/// ```rust
+/// use marvelsnapdeck::{DeckList, OrderInsensitive};
+///
+/// let mut first = DeckList::new();
+/// first.set_cards(&["Thanos", "AntMan"]);
+/// let mut second = DeckList::new();
+/// second.set_name("Thanos".into());
+/// second.set_cards(&["AntMan", "Thanos"]);
+///
+/// assert_ne!(first, second);
+/// assert_eq!(OrderInsensitive(&first), OrderInsensitive(&second));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct OrderInsensitive<'a>(pub &'a DeckList);
+
+impl PartialEq for OrderInsensitive<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.same_cards(other.0)
+    }
+}
+
+impl Eq for OrderInsensitive<'_> {}
+
+/// Build a [`DeckList`] through chained calls
+///
+/// # Example
+///
+/// ```rust
+/// use marvelsnapdeck::DeckListBuilder;
+///
+/// let list = DeckListBuilder::new()
+///     .with_name("Thanos")
+///     .cards(&["AntMan", "Thanos"])
+///     .build();
+///
+/// assert_eq!(list.name(), "Thanos");
+/// assert_eq!(list.cards(), vec!["AntMan", "Thanos"]);
+/// ```
+#[derive(Default, Debug, Clone)]
+pub struct DeckListBuilder {
+    list: DeckList,
+}
+
+impl DeckListBuilder {
+    /// Create a builder for an empty DeckList
+    pub fn new() -> Self {
+        Self {
+            list: DeckList::new(),
+        }
+    }
+
+    /// Create a builder starting from a decoded code, to duplicate and modify a deck
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::{DeckList, DeckListBuilder};
+    ///
+    /// let mut original = DeckList::new();
+    /// original.set_name("Thanos".into());
+    /// original.set_cards(&["AntMan", "Thanos"]);
+    /// let code = original.into_code().unwrap();
+    ///
+    /// let copy = DeckListBuilder::from_code(&code)
+    ///     .unwrap()
+    ///     .with_name("Thanos (copy)")
+    ///     .build();
+    ///
+    /// assert_eq!(copy.name(), "Thanos (copy)");
+    /// assert_eq!(copy.cards(), original.cards());
+    /// ```
+    pub fn from_code(code: &str) -> Result<Self, DeckListError> {
+        Ok(Self {
+            list: DeckList::from_code(code)?,
+        })
+    }
+
+    /// Set the deck name visible to the player in game
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.list.name = name.into();
+        self
+    }
+
+    /// Set the list of cards, replacing any already added
+    pub fn cards<T: AsRef<str> + std::fmt::Display>(mut self, cards: &[T]) -> Self {
+        self.list.set_cards(cards);
+        self
+    }
+
+    /// Add a single card after any already added, for building decks in a loop
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use marvelsnapdeck::DeckListBuilder;
+    ///
+    /// let mut builder = DeckListBuilder::new().with_name("Thanos");
+    /// for card in ["AntMan", "Nebula", "Thanos"] {
+    ///     if card != "Nebula" {
+    ///         builder = builder.add_card(card);
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(builder.build().cards(), vec!["AntMan", "Thanos"]);
+    /// ```
+    pub fn add_card(mut self, card: impl Into<String>) -> Self {
+        self.list.cards.push(Card {
+            name: card.into(),
+            meta: None,
+        });
+        self
+    }
+
+    /// Finish building the DeckList
+    pub fn build(self) -> DeckList {
+        self.list
+    }
+}
+
+/// Convert a code into a DeckList, the same as [`DeckList::from_code`]
+///
+/// # Example
+///
+/// ```rust
+/// use marvelsnapdeck::{decode, encode, DeckList};
+///
+/// let code = encode(&DeckList::new()).unwrap();
+///
+/// assert_eq!(decode(&code).unwrap(), DeckList::new());
+/// ```
+pub fn decode(code: &str) -> Result<DeckList, DeckListError> {
+    DeckList::from_code(code)
+}
+
+/// Convert a DeckList into a code, the same as [`DeckList::into_code`]
+pub fn encode(deck: &DeckList) -> Result<String, DeckListError> {
+    deck.into_code()
+}
+
+/// The code of an empty deck, handy as a placeholder or form default
+///
+/// # Example
+///
+/// ```rust
+/// use marvelsnapdeck::{empty_code, DeckList};
+///
+/// assert_eq!(DeckList::from_code(empty_code()).unwrap(), DeckList::new());
+/// ```
+pub fn empty_code() -> String {
+    DeckList::new()
+        .into_code()
+        .expect("an empty deck always encodes")
+}
+
+/// Decode the compact `name|code` sharing format, with the name taken from before `sep`.
+///
+/// The name overrides the one stored in the code. The string is split on the last `sep`,
+/// so names may contain it too. Without a separator the whole string is decoded as a code.
+///
+/// The separator must not be a character codes are made of, so letters, digits, `+`, `/`
+/// and `=` return [`DeckListError::InvalidSeparator`]. The same goes for characters of
+/// the [`KNOWN_PREFIXES`], such as the `:` in `MSD:`.
+///
+/// # Example
+///
+/// ```rust
+/// use marvelsnapdeck::{parse_named_code, DeckList};
+///
+/// let mut list = DeckList::new();
+/// list.set_name("Thanos".into());
+/// let shared = format!("Thanos v2|{}", list.into_code().unwrap());
+///
+/// assert_eq!(parse_named_code(&shared, '|').unwrap().name(), "Thanos v2");
+/// ```
+pub fn parse_named_code(s: &str, sep: char) -> Result<DeckList, DeckListError> {
+    if sep.is_ascii_alphanumeric()
+        || matches!(sep, '+' | '/' | '=')
+        || KNOWN_PREFIXES.iter().any(|prefix| prefix.contains(sep))
+    {
+        return Err(DeckListError::InvalidSeparator(sep));
+    }
+
+    match s.rsplit_once(sep) {
+        Some((name, code)) => {
+            let mut list = DeckList::from_code(code.trim())?;
+            list.set_name(name.trim().to_string());
+            Ok(list)
+        }
+        None => DeckList::from_code(s.trim()),
+    }
+}
+
+/// The code the game produces for a known deck, used by [`self_test`]
+const KNOWN_CODE: &str = "eyJOYW1lIjoiVGhhbm9zIiwiQ2FyZHMiOlt7IkNhcmREZWZJZCI6IkFudE1hbiJ9LHsiQ2FyZERlZklkIjoiQWdlbnQxMyJ9LHsiQ2FyZERlZklkIjoiUXVpbmpldCJ9LHsiQ2FyZERlZklkIjoiQW5nZWxhIn0seyJDYXJkRGVmSWQiOiJPa295ZSJ9LHsiQ2FyZERlZklkIjoiQXJtb3IifSx7IkNhcmREZWZJZCI6IkZhbGNvbiJ9LHsiQ2FyZERlZklkIjoiTXlzdGlxdWUifSx7IkNhcmREZWZJZCI6IkxvY2tqYXcifSx7IkNhcmREZWZJZCI6IkthWmFyIn0seyJDYXJkRGVmSWQiOiJEZXZpbERpbm9zYXVyIn0seyJDYXJkRGVmSWQiOiJUaGFub3MifV19";
+
+/// Check that this build encodes decks exactly like the game does.
+///
+/// A fixed deck is encoded and compared against the code the game produced for it.
+/// Running this at startup catches changes in serde or base64 output after dependency
+/// upgrades before they produce codes the game cannot read.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(marvelsnapdeck::self_test());
+/// ```
+pub fn self_test() -> bool {
+    let mut list = DeckList::new();
+    list.set_name("Thanos".into());
+    list.set_cards(&[
+        "AntMan",
+        "Agent13",
+        "Quinjet",
+        "Angela",
+        "Okoye",
+        "Armor",
+        "Falcon",
+        "Mystique",
+        "Lockjaw",
+        "KaZar",
+        "DevilDinosaur",
+        "Thanos",
+    ]);
+
+    list.into_code().is_ok_and(|code| code == KNOWN_CODE)
+}
+
+/// The scheme in front of the code in a QR payload, see [`DeckList::to_qr_payload`]
+pub const QR_SCHEME: &str = "msd://";
+
+/// Prefixes other tools are known to add in front of a code, such as `MSD:`
+pub const KNOWN_PREFIXES: &[&str] = &["MSD:"];
+
+/// Remove one of the [`KNOWN_PREFIXES`] from the start of a code.
+///
+/// To recognize additional prefixes, use [`strip_prefixes`] with your own list.
+///
+/// # Example
+///
+/// ```rust
+/// use marvelsnapdeck::strip_known_prefixes;
+///
+/// assert_eq!(strip_known_prefixes("MSD:eyJOYW1l"), "eyJOYW1l");
+/// assert_eq!(strip_known_prefixes("eyJOYW1l"), "eyJOYW1l");
+/// ```
+pub fn strip_known_prefixes(code: &str) -> &str {
+    strip_prefixes(code, KNOWN_PREFIXES)
+}
+
+/// Remove the first matching prefix from the start of a code.
+///
+/// [`DeckList::from_code`] removes the [`KNOWN_PREFIXES`] itself, but
+/// [`DeckList::from_code_with`] does not, so call this before it, or whenever other
+/// tools add prefixes of their own.
+///
+/// # Example
+///
+/// ```rust
+/// use marvelsnapdeck::{strip_prefixes, KNOWN_PREFIXES};
+///
+/// let prefixes = [KNOWN_PREFIXES, &["snap:"]].concat();
+///
+/// assert_eq!(strip_prefixes("snap:eyJOYW1l", &prefixes), "eyJOYW1l");
+/// ```
+pub fn strip_prefixes<'a>(code: &'a str, prefixes: &[&str]) -> &'a str {
+    prefixes
+        .iter()
+        .find_map(|prefix| code.strip_prefix(prefix))
+        .unwrap_or(code)
+}
+
+fn strip_prefixes_bytes<'a>(code: &'a [u8], prefixes: &[&str]) -> &'a [u8] {
+    prefixes
+        .iter()
+        .find_map(|prefix| code.strip_prefix(prefix.as_bytes()))
+        .unwrap_or(code)
+}
+
+/// The base64 encoding of `{"Name`, which every code exported by the game starts with
+const CODE_PREFIX: &str = "eyJOYW1l";
+
+/// Shortest code worth considering, an empty deck encodes to 30 characters
+const MIN_CODE_LEN: usize = 30;
+
+/// Longest code worth considering, a full deck is usually well under a kilobyte
+const MAX_CODE_LEN: usize = 4096;
+
+/// Quickly check whether a string looks like a deck code without decoding it.
+///
+/// This only checks the length, the base64 alphabet and the expected prefix, so
+/// it is a heuristic and may return true for strings that fail to decode. Use it
+/// to cheaply filter candidates before calling [`DeckList::from_code`].
+///
+/// # Example
+///
+/// ```rust
+/// use marvelsnapdeck::{looks_like_code, DeckList};
+///
+/// let mut list = DeckList::new();
+/// list.set_name("Thanos".into());
+/// let code = list.into_code().unwrap();
+///
+/// assert!(looks_like_code(&code));
+/// assert!(!looks_like_code("hello world"));
+/// ```
+pub fn looks_like_code(s: &str) -> bool {
+    (MIN_CODE_LEN..=MAX_CODE_LEN).contains(&s.len())
+        && s.starts_with(CODE_PREFIX)
+        && s.bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'/')
+}
+
+/// Find every deck code inside a larger piece of text, such as a chat message.
+///
+/// The text is split on anything that cannot be part of a code, candidates are
+/// filtered with [`looks_like_code`] and only those that decode are returned.
+/// Since backticks split the text too, codes pasted inside markdown code fences or
+/// inline code spans are found as well.
+///
+/// # Example
+///
+/// ```rust
+/// use marvelsnapdeck::{extract_codes, DeckList};
+///
+/// let code = DeckList::new().into_code().unwrap();
+/// let text = format!("Try this one: {} and let me know!", code);
+///
+/// assert_eq!(extract_codes(&text), vec![code.as_str()]);
+/// ```
+pub fn extract_codes(text: &str) -> Vec<&str> {
+    text.split(|c: char| !(c.is_ascii_alphanumeric() || c == '+' || c == '/'))
+        .filter(|candidate| looks_like_code(candidate))
+        .filter(|candidate| DeckList::from_code(candidate).is_ok())
+        .collect()
+}
+
+/// Convert a card display name into the definition id style the game uses.
+///
+/// Whitespace, `-` and `_` separate words, and each word starts with an uppercase
+/// letter, so `Ant-Man` becomes `AntMan` and `Devil Dinosaur` becomes `DevilDinosaur`.
+/// Other punctuation is dropped without starting a new word, so `Mister Negative's`
+/// becomes `MisterNegatives`. Letters inside a word are kept as typed, and cards whose ids
+/// differ from their display name in other ways cannot be inferred.
+///
+/// # Example
+///
+/// ```rust
+/// use marvelsnapdeck::to_def_id;
+///
+/// assert_eq!(to_def_id("Ant-Man"), "AntMan");
+/// assert_eq!(to_def_id("Ka-Zar"), "KaZar");
+/// assert_eq!(to_def_id("Agent 13"), "Agent13");
+/// ```
+pub fn to_def_id(name: &str) -> String {
+    name.split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+        .flat_map(|word| {
+            let mut chars = word.chars().filter(|c| c.is_alphanumeric());
+            chars
+                .next()
+                .into_iter()
+                .flat_map(char::to_uppercase)
+                .chain(chars)
+        })
+        .collect()
+}
+
+/// Check if a code decodes and re-encodes to exactly the same string.
+///
+/// Codes produced by the game always round-trip, so a false result points to a
+/// code that failed to decode or was written by a non-conforming tool, for
+/// example with reordered JSON keys or extra whitespace.
+///
+/// # Example
+///
+/// ```rust
+/// use base64::{engine::general_purpose::STANDARD_NO_PAD, Engine};
+/// use marvelsnapdeck::{is_canonical_code, DeckList};
+///
+/// let code = DeckList::new().into_code().unwrap();
+/// assert!(is_canonical_code(&code));
+///
+/// let reordered = STANDARD_NO_PAD.encode(r#"{"Cards":[],"Name":""}"#);
+/// assert!(!is_canonical_code(&reordered));
+/// ```
+pub fn is_canonical_code(code: &str) -> bool {
+    DeckList::from_code(code)
+        .and_then(|list| list.into_code())
+        .map(|encoded| encoded == code)
+        .unwrap_or(false)
+}
+
+/// Check if two codes describe the same cards, even when formatted differently.
+///
+/// Both codes are decoded with [`DeckList::from_code_lenient`] and compared with
+/// [`DeckList::same_cards`], so names and card order are ignored. Codes that fail to
+/// decode are never equivalent.
+///
+/// # Example
+///
+/// ```rust
+/// use marvelsnapdeck::{codes_equivalent, DeckList};
+///
+/// let mut first = DeckList::new();
+/// first.set_cards(&["AntMan", "Thanos"]);
+/// let mut second = DeckList::new();
+/// second.set_name("Thanos".into());
+/// second.set_cards(&["Thanos", "AntMan"]);
+///
+/// let a = first.into_code().unwrap();
+/// let b = format!("{}\n", second.into_code().unwrap());
+///
+/// assert!(codes_equivalent(&a, &b));
+/// assert!(!codes_equivalent(&a, "not a code"));
+/// ```
+pub fn codes_equivalent(a: &str, b: &str) -> bool {
+    match (
+        DeckList::from_code_lenient(a),
+        DeckList::from_code_lenient(b),
+    ) {
+        (Ok(a), Ok(b)) => a.same_cards(&b),
+        _ => false,
+    }
+}
+
+/// Find the first deck holding the same cards as a code, ignoring names and card order.
+///
+/// Returns `Ok(None)` when the code decodes but no deck matches.
+///
+/// # Example
+///
+/// ```rust
+/// use marvelsnapdeck::{find_matching, DeckList};
+///
+/// let mut saved = DeckList::new();
+/// saved.set_name("Thanos".into());
+/// saved.set_cards(&["AntMan", "Thanos"]);
+///
+/// let mut shared = DeckList::new();
+/// shared.set_cards(&["Thanos", "AntMan"]);
+/// let code = shared.into_code().unwrap();
+///
+/// let saved = [saved];
+/// let found = find_matching(&code, &saved).unwrap();
+/// assert_eq!(found.unwrap().name(), "Thanos");
+/// ```
+pub fn find_matching<'a, I: IntoIterator<Item = &'a DeckList>>(
+    code: &str,
+    decks: I,
+) -> Result<Option<&'a DeckList>, DeckListError> {
+    let list = DeckList::from_code(code)?;
+
+    Ok(decks.into_iter().find(|deck| deck.same_cards(&list)))
+}
+
+/// Find pairs of decks whose [`DeckList::edit_distance`] is at most `max_distance`.
+///
+/// Every pair is compared, so this is O(n²) and best suited to a player's saved decks
+/// rather than large datasets. Each pair is listed once, in collection order.
+///
+/// # Example
+///
+/// ```rust
+/// use marvelsnapdeck::{find_near_duplicates, DeckList};
+///
+/// let mut first = DeckList::new();
+/// first.set_cards(&["AntMan", "Thanos", "Lockjaw"]);
+/// let mut second = DeckList::new();
+/// second.set_cards(&["AntMan", "Thanos", "Nebula"]);
+/// let mut third = DeckList::new();
+/// third.set_cards(&["Deadpool", "Carnage"]);
+///
+/// let decks = [first, second, third];
+/// let pairs = find_near_duplicates(&decks, 2);
+///
+/// assert_eq!(pairs.len(), 1);
+/// assert!(pairs[0].0.same_cards(&decks[0]));
+/// ```
+pub fn find_near_duplicates(
+    decks: &[DeckList],
+    max_distance: usize,
+) -> Vec<(&DeckList, &DeckList)> {
+    decks
+        .iter()
+        .enumerate()
+        .flat_map(|(i, a)| decks[i + 1..].iter().map(move |b| (a, b)))
+        .filter(|(a, b)| a.edit_distance(b) <= max_distance)
+        .collect()
+}
+
+/// Check two collections hold the same decks, in any order.
+///
+/// Decks are compared with [`DeckList::same_cards`], ignoring deck names and card
+/// order, and a deck saved twice must appear twice in both collections. Decks are
+/// matched up by [`DeckList::fingerprint`] first, so large collections stay fast.
+///
+/// # Example
+///
+/// ```rust
+/// use marvelsnapdeck::{deck_sets_equal, DeckList};
+///
+/// let mut first = DeckList::new();
+/// first.set_cards(&["AntMan", "Thanos"]);
+/// let mut second = DeckList::new();
+/// second.set_cards(&["Nebula"]);
+/// let renamed = first.clone_with_name("Thanos");
+///
+/// assert!(deck_sets_equal(&[first.clone(), second.clone()], &[second.clone(), renamed]));
+/// assert!(!deck_sets_equal(&[first.clone(), second.clone()], &[first.clone(), first]));
+/// ```
+pub fn deck_sets_equal(a: &[DeckList], b: &[DeckList]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut unmatched: HashMap<u64, Vec<&DeckList>> = HashMap::new();
+    for deck in a {
+        unmatched.entry(deck.fingerprint()).or_default().push(deck);
+    }
+
+    b.iter().all(|deck| {
+        let Some(candidates) = unmatched.get_mut(&deck.fingerprint()) else {
+            return false;
+        };
+        match candidates.iter().position(|other| other.same_cards(deck)) {
+            Some(index) => {
+                candidates.swap_remove(index);
+                true
+            }
+            None => false,
+        }
+    })
+}
+
+/// Count how many decks include each card.
+///
+/// Cards are counted once per deck they appear in, extra copies in the same deck
+/// are not counted again.
+///
+/// # Example
+///
+/// ```rust
+/// use marvelsnapdeck::{card_popularity, DeckList};
+///
+/// let mut first = DeckList::new();
+/// first.set_cards(&["AntMan", "Thanos"]);
+/// let mut second = DeckList::new();
+/// second.set_cards(&["Thanos"]);
+///
+/// let popularity = card_popularity(&[first, second]);
+/// assert_eq!(popularity["Thanos"], 2);
+/// assert_eq!(popularity["AntMan"], 1);
+/// ```
+pub fn card_popularity<'a, I: IntoIterator<Item = &'a DeckList>>(
+    decks: I,
+) -> HashMap<String, usize> {
+    let mut popularity = HashMap::new();
+    for deck in decks {
+        for name in deck.card_set() {
+            *popularity.entry(name.to_string()).or_insert(0) += 1;
+        }
+    }
+    popularity
+}
+
+/// Count the decks that contain a card, matching the name exactly
+///
+/// # Example
+///
+/// ```rust
+/// use marvelsnapdeck::{decks_using, DeckList};
+///
+/// let mut first = DeckList::new();
+/// first.set_cards(&["AntMan", "Thanos"]);
+/// let mut second = DeckList::new();
+/// second.set_cards(&["Thanos"]);
+///
+/// assert_eq!(decks_using("Thanos", &[first, second]), 2);
+/// ```
+pub fn decks_using<'a, I: IntoIterator<Item = &'a DeckList>>(card: &str, decks: I) -> usize {
+    decks
+        .into_iter()
+        .filter(|deck| deck.iter_cards().any(|name| name == card))
+        .count()
+}
+
+/// Import decks from a JSON object mapping deck names to codes, like
+/// `{"Thanos": "eyJ...", "Destroy": "eyJ..."}`.
+///
+/// Each key replaces the name stored in its code, and decks are returned sorted by
+/// key. An entry that is not a valid code fails with [`DeckListError::InvalidEntry`]
+/// naming its key.
+///
+/// # Example
+///
+/// ```rust
+/// use marvelsnapdeck::{from_named_map_json, DeckList};
+///
+/// let code = DeckList::new().into_code().unwrap();
+/// let decks = from_named_map_json(&format!(r#"{{"Thanos": "{code}"}}"#)).unwrap();
+///
+/// assert_eq!(decks[0].name(), "Thanos");
+/// ```
+pub fn from_named_map_json(json: &str) -> Result<Vec<DeckList>, DeckListError> {
+    let map: BTreeMap<String, serde_json::Value> =
+        serde_json::from_str(json).map_err(|_| DeckListError::InvalidDeckInput)?;
+
+    map.into_iter()
+        .map(|(key, value)| {
+            let list = value
+                .as_str()
+                .ok_or(DeckListError::InvalidDeckInput)
+                .and_then(DeckList::from_code);
+            match list {
+                Ok(mut list) => {
+                    list.set_name(key);
+                    Ok(list)
+                }
+                Err(source) => Err(DeckListError::InvalidEntry {
+                    key,
+                    source: Box::new(source),
+                }),
+            }
+        })
+        .collect()
+}
+
+/// Export decks as a JSON object mapping each deck name to its code, for backups.
+///
+/// Later decks sharing a name are stored as `Name (2)`, `Name (3)` and so on so no deck
+/// is lost. The result is read back by [`from_named_map_json`].
+///
+/// # Example
+///
+/// ```rust
+/// use marvelsnapdeck::{to_named_map_json, DeckList};
+///
+/// let mut list = DeckList::new();
+/// list.set_name("Thanos".into());
+///
+/// let json = to_named_map_json(&[list.clone(), list]).unwrap();
+///
+/// assert!(json.contains(r#""Thanos":"#));
+/// assert!(json.contains(r#""Thanos (2)":"#));
+/// ```
+pub fn to_named_map_json<'a, I: IntoIterator<Item = &'a DeckList>>(
+    decks: I,
+) -> Result<String, DeckListError> {
+    let mut map = BTreeMap::new();
+    for deck in decks {
+        let mut key = deck.name.clone();
+        let mut index = 1;
+        while map.contains_key(&key) {
+            index += 1;
+            key = format!("{} ({index})", deck.name);
+        }
+        map.insert(key, deck.into_code()?);
+    }
+
+    serde_json::to_string(&map).map_err(|_| DeckListError::EncodingError)
+}
+
+/// Exports many decks as one CSV in the [`DeckList::to_csv`] format, under a single header.
+///
+/// # Example
+///
+/// ```rust
+/// use marvelsnapdeck::{decks_to_csv, DeckList};
+///
+/// let mut first = DeckList::new();
+/// first.set_name("Thanos".into());
+/// first.set_cards(&["AntMan"]);
+/// let mut second = DeckList::new();
+/// second.set_name("Zoo".into());
+/// second.set_cards(&["Nightcrawler"]);
+///
+/// assert_eq!(
+///     decks_to_csv(&[first, second]),
+///     "deck,card\nThanos,AntMan\nZoo,Nightcrawler\n"
+/// );
+/// ```
+pub fn decks_to_csv<'a, I: IntoIterator<Item = &'a DeckList>>(decks: I) -> String {
+    let mut csv = String::from(CSV_HEADER);
+    for deck in decks {
+        deck.write_csv_rows(&mut csv);
+    }
+    csv
+}
+
+/// Imports decks from CSV in the [`decks_to_csv`] format, grouping rows by the `deck` column.
+///
+/// Decks are returned in the order their first row appears. Blank lines are skipped and
+/// quoted fields may span several lines. A missing header or a row without exactly two
+/// fields is reported with the line number the row starts on.
+///
+/// # Example
+///
+/// ```rust
+/// use marvelsnapdeck::decks_from_csv;
+///
+/// let decks = decks_from_csv("deck,card\nThanos,AntMan\nZoo,Nightcrawler\nThanos,Thanos\n").unwrap();
+/// assert_eq!(decks.len(), 2);
+/// assert_eq!(decks[0].name(), "Thanos");
+/// assert_eq!(decks[0].cards().len(), 2);
+/// ```
+pub fn decks_from_csv(csv: &str) -> Result<Vec<DeckList>, DeckListError> {
+    let error = |line: usize, msg: &str| DeckListError::ParseError {
+        line,
+        msg: msg.to_string(),
+    };
+    let mut records = parse_csv_records(csv.trim_start_matches('\u{feff}'))?.into_iter();
+
+    match records.next() {
+        Some((_, header)) if header.iter().map(|field| field.trim()).eq(["deck", "card"]) => {}
+        Some((line, _)) => return Err(error(line, "expected a deck,card header")),
+        None => return Err(error(1, "expected a deck,card header")),
+    }
+
+    let mut decks: Vec<DeckList> = Vec::new();
+    let mut by_name = HashMap::new();
+    for (line, fields) in records {
+        let [name, card] = <[String; 2]>::try_from(fields)
+            .map_err(|fields| error(line, &format!("expected 2 fields, found {}", fields.len())))?;
+        if card.is_empty() {
+            return Err(error(line, "card is empty"));
+        }
+
+        let position = *by_name.entry(name.clone()).or_insert_with(|| {
+            let mut deck = DeckList::new();
+            deck.set_name(name);
+            decks.push(deck);
+            decks.len() - 1
+        });
+        decks[position].cards.push(Card {
+            name: card,
+            meta: None,
+        });
+    }
+    Ok(decks)
+}
+
+/// Split CSV into records of fields, undoing the quoting applied by [`csv_field`].
+///
+/// Each record comes with the one-based line it starts on. Blank lines are skipped.
+fn parse_csv_records(csv: &str) -> Result<Vec<(usize, Vec<String>)>, DeckListError> {
+    let mut records = Vec::new();
+    let mut chars = csv.chars().peekable();
+    let mut line = 1;
+
+    while chars.peek().is_some() {
+        let start = line;
+        let error = |msg: &str| DeckListError::ParseError {
+            line: start,
+            msg: msg.to_string(),
+        };
+        let mut fields = Vec::new();
+        let mut quoted = false;
+
+        loop {
+            let mut field = String::new();
+            if chars.peek() == Some(&'"') {
+                quoted = true;
+                chars.next();
+                loop {
+                    match chars.next() {
+                        Some('"') if chars.peek() == Some(&'"') => {
+                            chars.next();
+                            field.push('"');
+                        }
+                        Some('"') => break,
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            field.push(c);
+                        }
+                        None => return Err(error("unterminated quoted field")),
+                    }
+                }
+                if !matches!(chars.peek(), None | Some(',' | '\n' | '\r')) {
+                    return Err(error("unexpected text after quoted field"));
+                }
+            } else {
+                while let Some(c) = chars.next_if(|c| !matches!(c, ',' | '\n' | '\r')) {
+                    if c == '"' {
+                        return Err(error("unexpected quote in unquoted field"));
+                    }
+                    field.push(c);
+                }
+            }
+            fields.push(field);
+
+            match chars.next() {
+                Some(',') => continue,
+                Some('\r') => {
+                    chars.next_if_eq(&'\n');
+                }
+                _ => {}
+            }
+            line += 1;
+            break;
+        }
+
+        let blank = !quoted && fields.len() == 1 && fields[0].trim().is_empty();
+        if !blank {
+            records.push((start, fields));
+        }
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+// The original tests are kept as they were written, before these lints
+#[allow(
+    clippy::redundant_static_lifetimes,
+    clippy::needless_borrows_for_generic_args,
+    clippy::unnecessary_to_owned
+)]
+mod tests {
+    use crate::{
+        card_popularity, codes_equivalent, decks_from_csv, decks_to_csv, decks_using, empty_code,
+        extract_codes, from_named_map_json, looks_like_code, parse_named_code, self_test,
+        to_def_id, to_named_map_json, CardMeta, DeckCode, DeckList, DeckListError,
+        OrderInsensitive,
+    };
+    use base64::{engine::general_purpose, Engine};
+    use std::collections::HashSet;
+
+    const VALID_CODE: &'static str = "eyJOYW1lIjoiVGhhbm9zIiwiQ2FyZHMiOlt7IkNhcmREZWZJZCI6IkFudE1hbiJ9LHsiQ2FyZERlZklkIjoiQWdlbnQxMyJ9LHsiQ2FyZERlZklkIjoiUXVpbmpldCJ9LHsiQ2FyZERlZklkIjoiQW5nZWxhIn0seyJDYXJkRGVmSWQiOiJPa295ZSJ9LHsiQ2FyZERlZklkIjoiQXJtb3IifSx7IkNhcmREZWZJZCI6IkZhbGNvbiJ9LHsiQ2FyZERlZklkIjoiTXlzdGlxdWUifSx7IkNhcmREZWZJZCI6IkxvY2tqYXcifSx7IkNhcmREZWZJZCI6IkthWmFyIn0seyJDYXJkRGVmSWQiOiJEZXZpbERpbm9zYXVyIn0seyJDYXJkRGVmSWQiOiJUaGFub3MifV19";
+
+    #[test]
+    fn decode_is_valid() {
+        let list = DeckList::from_code(&VALID_CODE.to_string()).unwrap();
+        assert_eq!(list.name(), "Thanos");
+        assert_eq!(list.cards.len(), 12);
+    }
+
+    #[test]
+    fn decode_cards() {
+        let list = DeckList::from_code(&VALID_CODE.to_string()).unwrap();
+        let cards = list.cards();
+
+        assert_eq!(cards.len(), 12);
+        assert_eq!(cards[0], "AntMan");
+    }
+
+    #[test]
+    fn encode_cards() {
+        let mut list = DeckList::new();
+        list.set_name("Thanos".to_string());
+        list.set_cards(&[
+            "AntMan",
+            "Agent13",
+            "Quinjet",
             "Angela",
             "Okoye",
             "Armor",
@@ -242,6 +3922,809 @@ mod tests {
             "Thanos",
         ]);
         let code = list.into_code().unwrap();
-        assert_eq!(code, VALID_CODE.to_string());
+        assert_eq!(code, VALID_CODE.to_string());
+    }
+
+    #[test]
+    fn json_bytes_match_code() {
+        let list = DeckList::from_code(VALID_CODE).unwrap();
+        let bytes = list.to_json_bytes().unwrap();
+
+        assert_eq!(general_purpose::STANDARD_NO_PAD.encode(bytes), VALID_CODE);
+    }
+
+    #[test]
+    fn decode_empty_input() {
+        assert!(matches!(
+            DeckList::from_code(""),
+            Err(DeckListError::InvalidDeckInput)
+        ));
+        assert!(matches!(
+            DeckList::from_code(" \n\t"),
+            Err(DeckListError::InvalidDeckInput)
+        ));
+    }
+
+    #[test]
+    fn decode_with_known_prefix() {
+        let list = DeckList::from_code(format!("MSD:{}", VALID_CODE)).unwrap();
+        assert_eq!(list.name(), "Thanos");
+    }
+
+    #[test]
+    fn diff_text_output() {
+        let mut before = DeckList::new();
+        before.set_cards(&["AntMan", "Nebula", "Thanos", "Lockjaw"]);
+        let mut after = DeckList::new();
+        after.set_cards(&["AntMan", "Thanos", "Sunspot", "Hawkeye"]);
+
+        assert_eq!(
+            before.diff_text(&after),
+            "+ Sunspot\n+ Hawkeye\n- Nebula\n- Lockjaw"
+        );
+        assert_eq!(before.diff_text(&before), "");
+    }
+
+    #[test]
+    fn decode_single_deck_array() {
+        let code = general_purpose::STANDARD_NO_PAD
+            .encode(r#"[{"Name":"Thanos","Cards":[{"CardDefId":"Thanos"}]}]"#);
+        let list = DeckList::from_code(code).unwrap();
+
+        assert_eq!(list.name(), "Thanos");
+        assert_eq!(list.cards(), vec!["Thanos"]);
+    }
+
+    #[test]
+    fn decode_multiple_deck_array() {
+        let code = general_purpose::STANDARD_NO_PAD
+            .encode(r#"[{"Name":"Thanos","Cards":[]},{"Name":"Destroy","Cards":[]}]"#);
+
+        assert!(matches!(
+            DeckList::from_code(code),
+            Err(DeckListError::MultipleDecks)
+        ));
+    }
+
+    #[test]
+    fn decode_many_from_single_deck() {
+        let lists = DeckList::many_from_code(VALID_CODE).unwrap();
+
+        assert_eq!(lists.len(), 1);
+        assert_eq!(lists[0].name(), "Thanos");
+    }
+
+    #[test]
+    fn short_id_is_stable() {
+        let list = DeckList::from_code(VALID_CODE).unwrap();
+        let mut reordered = list.clone();
+        reordered.set_name("Other".into());
+        reordered.cards.reverse();
+
+        assert_eq!(list.short_id(), reordered.short_id());
+        assert_ne!(list.short_id(), DeckList::new().short_id());
+        assert!(list
+            .short_id()
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || ('2'..='7').contains(&c)));
+    }
+
+    #[test]
+    fn clipboard_without_code() {
+        assert!(matches!(
+            DeckList::from_clipboard("Name: Thanos\nno code here"),
+            Err(DeckListError::InvalidDeckInput)
+        ));
+    }
+
+    #[test]
+    fn clipboard_keeps_decoded_name() {
+        let text = format!("Check out my deck\n\n{}\n", VALID_CODE);
+        let list = DeckList::from_clipboard(&text).unwrap();
+
+        assert_eq!(list.name(), "Thanos");
+        assert_eq!(list.cards().len(), 12);
+    }
+
+    #[test]
+    fn clipboard_ignores_blank_name() {
+        for text in [
+            format!("Name:\n{}\n", VALID_CODE),
+            format!("Name:   \n{}\n", VALID_CODE),
+        ] {
+            assert_eq!(DeckList::from_clipboard(&text).unwrap().name(), "Thanos");
+        }
+    }
+
+    #[test]
+    fn name_with_newline_is_invalid() {
+        let mut list = DeckList::new();
+
+        assert!(matches!(
+            list.try_set_name("Thanos\nLockjaw"),
+            Err(DeckListError::InvalidName)
+        ));
+        assert_eq!(list.name(), "");
+
+        list.set_name("Thanos\nLockjaw".into());
+        assert!(!list.name_is_valid());
+    }
+
+    #[test]
+    fn clipboard_in_markdown_fence() {
+        let text = format!("Name: Lockjaw\n```\n{}\n```", VALID_CODE);
+        let list = DeckList::from_clipboard(&text).unwrap();
+
+        assert_eq!(list.name(), "Lockjaw");
+        assert_eq!(list.cards().len(), 12);
+    }
+
+    #[test]
+    fn clipboard_in_inline_backticks() {
+        let text = format!("`Name: Lockjaw`\nhere you go `{}`", VALID_CODE);
+        let list = DeckList::from_clipboard(&text).unwrap();
+
+        assert_eq!(list.name(), "Lockjaw");
+        assert_eq!(extract_codes(&text), vec![VALID_CODE]);
+    }
+
+    #[test]
+    fn game_legal_deck() {
+        let list = DeckList::from_code(VALID_CODE).unwrap();
+
+        assert!(list.validate().is_ok());
+        assert!(list.is_game_legal());
+    }
+
+    #[test]
+    fn game_legal_requires_name() {
+        let mut list = DeckList::from_code(VALID_CODE).unwrap();
+        list.set_name("".into());
+
+        assert!(!list.is_game_legal());
+        assert!(matches!(
+            list.validate().unwrap_err()[..],
+            [DeckListError::EmptyName]
+        ));
+    }
+
+    #[test]
+    fn game_legal_requires_valid_name() {
+        let mut list = DeckList::from_code(VALID_CODE).unwrap();
+        list.set_name("Thanos\n".into());
+
+        assert!(!list.is_game_legal());
+        assert!(matches!(
+            list.validate().unwrap_err()[..],
+            [DeckListError::InvalidName]
+        ));
+    }
+
+    #[test]
+    fn game_legal_requires_twelve_cards() {
+        let mut list = DeckList::from_code(VALID_CODE).unwrap();
+        list.cards.pop();
+
+        assert!(!list.is_game_legal());
+        assert!(matches!(
+            list.validate().unwrap_err()[..],
+            [DeckListError::WrongCardCount(11)]
+        ));
+    }
+
+    #[test]
+    fn game_legal_requires_unique_cards() {
+        let mut list = DeckList::from_code(VALID_CODE).unwrap();
+        list.cards[1] = list.cards[0].clone();
+
+        assert!(!list.is_game_legal());
+        assert!(matches!(
+            &list.validate().unwrap_err()[..],
+            [DeckListError::DuplicateCard(name)] if name == "AntMan"
+        ));
+    }
+
+    #[test]
+    fn minimal_json_keeps_non_empty_name() {
+        let mut list = DeckList::new();
+        list.set_cards(&["AntMan"]);
+        assert!(!list.to_minimal_json().unwrap().contains("Name"));
+
+        list.set_name("Thanos".into());
+        assert_eq!(
+            list.to_minimal_json().unwrap().into_bytes(),
+            list.to_json_bytes().unwrap()
+        );
+    }
+
+    #[test]
+    fn codes_equivalent_across_formats() {
+        let url_safe = DeckList::from_code(VALID_CODE)
+            .unwrap()
+            .into_code_with(&general_purpose::URL_SAFE)
+            .unwrap();
+
+        assert!(codes_equivalent(VALID_CODE, &url_safe));
+        assert!(codes_equivalent(VALID_CODE, &format!("MSD:{}", VALID_CODE)));
+        assert!(!codes_equivalent(
+            VALID_CODE,
+            &DeckList::new().into_code().unwrap()
+        ));
+    }
+
+    #[test]
+    fn intersection_count_ignores_duplicates() {
+        let list = DeckList::from_code(VALID_CODE).unwrap();
+        let mut other = DeckList::new();
+        other.set_cards(&["Thanos", "Thanos", "AntMan", "Nebula"]);
+
+        assert_eq!(list.intersection_count(&other), 2);
+        assert_eq!(other.intersection_count(&list), 2);
+        assert_eq!(list.intersection_count(&list), 12);
+        assert_eq!(list.intersection_count(&DeckList::new()), 0);
+    }
+
+    #[cfg(feature = "compress")]
+    #[test]
+    fn compressed_round_trip() {
+        let list = DeckList::from_code(VALID_CODE).unwrap();
+        let compressed = list.into_code_compressed().unwrap();
+
+        assert!(compressed.len() < VALID_CODE.len() / 2);
+        assert_eq!(DeckList::from_code_compressed(compressed).unwrap(), list);
+    }
+
+    #[cfg(feature = "compress")]
+    #[test]
+    fn compressed_reads_gzip_wrapper() {
+        let gzipped = "H4sIAAAAAAACA2WQvQrCQBCE32XrNGKXTjwE0SiClWKxJGu85LKL96OEcO9uECu3G74ZhmEmOOBAUML5gSwBClijbwKU1+mrDN23zWyvOFbIkIs/3BLHxVLxU7LcUdR5bsmhwsdeRtJhP4hXdIOuFr2kGkO0z6Rb9lL3Hb4V3+EFdbuhl3XGzldg0u7vpHzLH6LQQkg4AQAA";
+        let list = DeckList::from_code_compressed(gzipped).unwrap();
+        assert_eq!(list, DeckList::from_code(VALID_CODE).unwrap());
+
+        let mut corrupt = general_purpose::STANDARD_NO_PAD.decode(gzipped).unwrap();
+        let crc = corrupt.len() - 8;
+        corrupt[crc] ^= 1;
+        assert!(matches!(
+            DeckList::from_code_compressed(general_purpose::STANDARD_NO_PAD.encode(corrupt)),
+            Err(DeckListError::DecompressionError)
+        ));
+    }
+
+    #[cfg(feature = "compress")]
+    #[test]
+    fn compressed_reads_raw_deflate_from_other_tools() {
+        let raw = "ZZC9CsJAEITfZes0YpdOPATRKIKVYrEka7zksov3o4Rw724QK7cbvhmGYSY44EBQwvmBLAEKWKNvApTX6asM3bfNbK84VsiQiz/cEsfFUvFTstxR1HluyaHCx15G0mE/iFd0g64WvaQaQ7TPpFv2UvcdvhXf4QV1u6GXdcbOV2DS7u+kfMsf";
+        let list = DeckList::from_code_compressed(raw).unwrap();
+        assert_eq!(list, DeckList::from_code(VALID_CODE).unwrap());
+    }
+
+    #[cfg(feature = "compress")]
+    #[test]
+    fn compressed_rejects_plain_code() {
+        assert!(matches!(
+            DeckList::from_code_compressed(VALID_CODE),
+            Err(DeckListError::DecompressionError)
+        ));
+    }
+
+    #[test]
+    fn sorted_cards_keeps_deck_order() {
+        let list = DeckList::from_code(VALID_CODE).unwrap();
+        let sorted = list.sorted_cards();
+
+        assert_eq!(sorted[0], "Agent13");
+        assert_eq!(sorted[11], "Thanos");
+        assert_eq!(list.cards()[0], "AntMan");
+        assert_eq!(list.into_code().unwrap(), VALID_CODE);
+    }
+
+    #[test]
+    fn duplicates_detected() {
+        let mut list = DeckList::from_code(VALID_CODE).unwrap();
+        assert!(!list.has_duplicates());
+        assert!(!DeckList::new().has_duplicates());
+
+        list.cards[11] = list.cards[3].clone();
+        assert!(list.has_duplicates());
+    }
+
+    #[test]
+    fn normalize_tracks_mixed_case() {
+        let mut list = DeckList::new();
+        list.set_cards(&["ant-man", "devil dinosaur", "Ka-Zar", "Thanos", "Ant Man"]);
+
+        let originals = list.normalize_card_names_tracked();
+
+        assert_eq!(
+            list.cards(),
+            vec!["AntMan", "DevilDinosaur", "KaZar", "Thanos", "AntMan"]
+        );
+        assert_eq!(originals.len(), 4);
+        assert_eq!(originals["AntMan"], "ant-man");
+        assert_eq!(originals["DevilDinosaur"], "devil dinosaur");
+        assert_eq!(originals["KaZar"], "Ka-Zar");
+        assert_eq!(originals["Thanos"], "Thanos");
+    }
+
+    #[test]
+    fn url_component_fully_encoded() {
+        let component: String = VALID_CODE.bytes().map(|b| format!("%{:02X}", b)).collect();
+        let list = DeckList::from_url_component(&component).unwrap();
+
+        assert_eq!(list.into_code().unwrap(), VALID_CODE);
+    }
+
+    #[test]
+    fn url_component_plus_and_slash() {
+        let mut list = DeckList::new();
+        list.set_name("Zoo???".into());
+        list.set_cards(&["Sunspot>", "Elektra~"]);
+        let code = list.into_code().unwrap();
+        let component = code.replace('+', "%2B").replace('/', "%2f");
+
+        assert_ne!(component, code);
+        assert_eq!(DeckList::from_url_component(&component).unwrap(), list);
+    }
+
+    #[test]
+    fn url_component_malformed() {
+        assert!(matches!(
+            DeckList::from_url_component("eyJOYW1l%2"),
+            Err(DeckListError::InvalidDeckInput)
+        ));
+        assert!(matches!(
+            DeckList::from_url_component("eyJOYW1l%ZZ"),
+            Err(DeckListError::InvalidDeckInput)
+        ));
+        assert!(matches!(
+            DeckList::from_url_component("eyJOYW1l%+A"),
+            Err(DeckListError::InvalidDeckInput)
+        ));
+    }
+
+    #[test]
+    fn csv_output() {
+        let mut list = DeckList::new();
+        list.set_name("Thanos, \"Lockjaw\"".into());
+        list.set_cards(&["AntMan", "Thanos"]);
+
+        assert_eq!(
+            list.to_csv(),
+            "deck,card\n\"Thanos, \"\"Lockjaw\"\"\",AntMan\n\"Thanos, \"\"Lockjaw\"\"\",Thanos\n"
+        );
+        assert_eq!(DeckList::new().to_csv(), "deck,card\n");
+    }
+
+    #[test]
+    fn csv_import_groups_decks() {
+        let mut first = DeckList::new();
+        first.set_name("Thanos, \"Lockjaw\"".into());
+        first.set_cards(&["AntMan", "Thanos"]);
+        let mut second = DeckList::new();
+        second.set_name("Zoo".into());
+        second.set_cards(&["Nightcrawler", "Squirrel Girl"]);
+
+        let decks = decks_from_csv(&decks_to_csv(&[first.clone(), second.clone()])).unwrap();
+        assert_eq!(decks, [first, second]);
+
+        let csv = "deck,card\r\nZoo,Nightcrawler\r\nThanos,AntMan\r\n\r\nZoo,Ebony Blade\r\n";
+        let decks = decks_from_csv(csv).unwrap();
+        assert_eq!(decks.len(), 2);
+        assert_eq!(decks[0].name(), "Zoo");
+        assert_eq!(decks[0].cards().len(), 2);
+        assert_eq!(decks[1].name(), "Thanos");
+    }
+
+    #[test]
+    fn csv_round_trip_with_newline_and_quotes() {
+        let mut first = DeckList::new();
+        first.set_name("Thanos\n\"v2\"".into());
+        first.set_cards(&["AntMan", "Thanos"]);
+        let mut second = DeckList::new();
+        second.set_name("Zoo".into());
+        second.set_cards(&["Nightcrawler"]);
+
+        let csv = decks_to_csv(&[first.clone(), second.clone()]);
+        assert_eq!(decks_from_csv(&csv).unwrap(), [first, second]);
+
+        let csv = "deck,card\n\"Thanos\nv2\",AntMan\nZoo,Nightcrawler,Thanos\n";
+        assert!(matches!(
+            decks_from_csv(csv),
+            Err(DeckListError::ParseError { line: 4, .. })
+        ));
+    }
+
+    #[test]
+    fn csv_import_reports_line() {
+        assert!(matches!(
+            decks_from_csv("name,card\nThanos,AntMan"),
+            Err(DeckListError::ParseError { line: 1, .. })
+        ));
+        assert!(matches!(
+            decks_from_csv("deck,card\nThanos,AntMan\nThanos,AntMan,Thanos"),
+            Err(DeckListError::ParseError { line: 3, .. })
+        ));
+        assert!(matches!(
+            decks_from_csv("deck,card\nThanos,AntMan\n\"Thanos,AntMan"),
+            Err(DeckListError::ParseError { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn restrict_to_owned_filters() {
+        let mut list = DeckList::from_code(VALID_CODE).unwrap();
+        list.set_canonical_ordering(true);
+        let owned: HashSet<String> = ["Thanos", "AntMan", "Nebula", "Quinjet"]
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+
+        let owned_list = list.restrict_to_owned(&owned);
+        assert_eq!(owned_list.name(), "Thanos");
+        assert_eq!(owned_list.cards(), vec!["AntMan", "Quinjet", "Thanos"]);
+        assert!(owned_list.canonical_ordering());
+        assert_eq!(list.cards().len(), 12);
+    }
+
+    #[test]
+    fn empty_code_decodes_to_empty_deck() {
+        let list = DeckList::from_code(empty_code()).unwrap();
+        assert_eq!(list.name(), "");
+        assert!(list.cards().is_empty());
+    }
+
+    #[test]
+    fn blank_card_is_empty() {
+        let mut list = DeckList::from_code(VALID_CODE).unwrap();
+        assert!(!list.has_empty_cards());
+
+        list.set_cards(&["AntMan", " \t", "Thanos"]);
+        assert!(list.has_empty_cards());
+        list.set_cards(&["AntMan", ""]);
+        assert!(list.has_empty_cards());
+    }
+
+    #[test]
+    fn tracker_json_counts() {
+        let json = r#"{"deckName": "Thanos", "cards": [
+            {"cardDefId": "AntMan", "count": 1},
+            {"cardDefId": "Nebula", "count": 0},
+            {"cardDefId": "Thanos"}
+        ]}"#;
+        let list = DeckList::from_tracker_json(json).unwrap();
+        assert_eq!(list.name(), "Thanos");
+        assert_eq!(list.cards(), vec!["AntMan", "Thanos"]);
+
+        let json = r#"{"cards": [{"cardDefId": "AntMan", "count": 2}]}"#;
+        assert!(matches!(
+            DeckList::from_tracker_json(json),
+            Err(DeckListError::CardCountTooHigh(name, 2)) if name == "AntMan"
+        ));
+    }
+
+    #[test]
+    fn clone_with_name_keeps_original() {
+        let list = DeckList::from_code(VALID_CODE).unwrap();
+        let copy = list.clone_with_name("Thanos v2");
+
+        assert_eq!(list.name(), "Thanos");
+        assert_eq!(copy.name(), "Thanos v2");
+        assert_eq!(copy.cards(), list.cards());
+        assert_eq!(list, DeckList::from_code(VALID_CODE).unwrap());
+    }
+
+    #[test]
+    fn named_code_with_separator() {
+        let list = parse_named_code(&format!("Snap | Thanos|{VALID_CODE}"), '|').unwrap();
+        assert_eq!(list.name(), "Snap | Thanos");
+        assert_eq!(list.cards().len(), 12);
+
+        assert!(parse_named_code("Thanos|not a code", '|').is_err());
+    }
+
+    #[test]
+    fn named_code_without_separator() {
+        let list = parse_named_code(VALID_CODE, '|').unwrap();
+        assert_eq!(list, DeckList::from_code(VALID_CODE).unwrap());
+    }
+
+    #[test]
+    fn named_code_rejects_base64_separator() {
+        for sep in ['/', '+', '=', 'a', 'Z', '0', ':'] {
+            assert!(matches!(
+                parse_named_code(&format!("Thanos{sep}{VALID_CODE}"), sep),
+                Err(DeckListError::InvalidSeparator(c)) if c == sep
+            ));
+        }
+
+        assert!(matches!(
+            parse_named_code(&format!("MSD:{VALID_CODE}"), ':'),
+            Err(DeckListError::InvalidSeparator(':'))
+        ));
+    }
+
+    #[test]
+    fn bitset_round_trip() {
+        let list = DeckList::from_code(VALID_CODE).unwrap();
+        let mut registry = list.sorted_cards();
+        registry.insert(3, "Nebula".to_string());
+        let registry: Vec<&str> = registry.iter().map(String::as_str).collect();
+
+        let bits = list.to_bitset(&registry).unwrap();
+        assert_eq!(bits.len(), 2);
+        let decoded = DeckList::from_bitset(&bits, &registry).unwrap();
+        assert!(decoded.same_cards(&list));
+
+        assert!(matches!(
+            list.to_bitset(&registry[1..]),
+            Err(DeckListError::UnknownCard(name)) if name == "Agent13"
+        ));
+        assert!(matches!(
+            DeckList::from_bitset(&[0, 0b100000], &registry),
+            Err(DeckListError::InvalidDeckInput)
+        ));
+    }
+
+    #[test]
+    fn order_insensitive_ignores_order_and_name() {
+        let list = DeckList::from_code(VALID_CODE).unwrap();
+        let mut reversed = list.clone_with_name("Reversed");
+        reversed.set_cards(&list.cards().into_iter().rev().collect::<Vec<_>>());
+
+        assert_ne!(list, reversed);
+        assert_eq!(OrderInsensitive(&list), OrderInsensitive(&reversed));
+    }
+
+    #[test]
+    fn order_insensitive_counts_duplicates() {
+        let mut first = DeckList::new();
+        first.set_cards(&["AntMan", "AntMan", "Thanos"]);
+        let mut second = DeckList::new();
+        second.set_cards(&["AntMan", "Thanos", "Thanos"]);
+
+        assert_ne!(OrderInsensitive(&first), OrderInsensitive(&second));
+    }
+
+    #[test]
+    fn self_test_passes() {
+        assert!(self_test());
+    }
+
+    #[test]
+    fn extra_fields_are_emitted() {
+        let mut list = DeckList::from_code(VALID_CODE).unwrap();
+        let extra = serde_json::json!({"Version": 2, "Name": "Ignored", "Art": {"Id": 7}});
+        list.apply_extra(extra.as_object().unwrap());
+
+        let code = list.into_code_with_extra().unwrap();
+        let json = general_purpose::STANDARD_NO_PAD.decode(&code).unwrap();
+        let json = String::from_utf8(json).unwrap();
+
+        assert!(json.starts_with(r#"{"Name":"Thanos","Cards":[{"CardDefId":"AntMan"}"#));
+        assert!(json.ends_with(r#"],"Art":{"Id":7},"Version":2}"#));
+        assert_eq!(list.into_code().unwrap(), VALID_CODE);
+        assert_eq!(DeckList::from_code(&code).unwrap().name(), "Thanos");
+    }
+
+    #[test]
+    fn extra_fields_ignored_by_equality() {
+        let mut list = DeckList::from_code(VALID_CODE).unwrap();
+        list.apply_extra(serde_json::json!({"Version": 2}).as_object().unwrap());
+
+        assert_eq!(list, DeckList::from_code(VALID_CODE).unwrap());
+        assert_eq!(
+            DeckList::from_code(list.into_code_with_extra().unwrap()).unwrap(),
+            list
+        );
+    }
+
+    #[test]
+    fn qr_payload_round_trip() {
+        let mut list = DeckList::from_code(VALID_CODE).unwrap();
+        list.set_name("Thanos???>>>".into());
+        let payload = list.to_qr_payload().unwrap();
+
+        assert!(payload.contains(['-', '_']));
+        assert_eq!(DeckList::from_qr_payload(&payload).unwrap(), list);
+        assert_eq!(
+            DeckList::from_qr_payload(VALID_CODE).unwrap(),
+            DeckList::from_code(VALID_CODE).unwrap()
+        );
+    }
+
+    #[test]
+    fn decks_using_counts_each_deck_once() {
+        let list = DeckList::from_code(VALID_CODE).unwrap();
+        let mut doubled = DeckList::new();
+        doubled.set_cards(&["AntMan", "AntMan"]);
+        let mut other = DeckList::new();
+        other.set_cards(&["Nebula", "antman"]);
+        let decks = [list, doubled, other];
+
+        assert_eq!(decks_using("AntMan", &decks), 2);
+        assert_eq!(decks_using("Nebula", &decks), 1);
+        assert_eq!(decks_using("Galactus", &decks), 0);
+    }
+
+    #[test]
+    fn name_with_quote_and_backslash_round_trips() {
+        let mut list = DeckList::from_code(VALID_CODE).unwrap();
+        assert!(list.name_is_json_safe());
+
+        list.set_name(r#"Thanos "v2" \ \"final\""#.into());
+        assert!(!list.name_is_json_safe());
+
+        let code = list.into_code().unwrap();
+        let json = general_purpose::STANDARD_NO_PAD.decode(&code).unwrap();
+        assert!(json.starts_with(br#"{"Name":"Thanos \"v2\" \\ \\\"final\\\"","#));
+        assert_eq!(DeckList::from_code(&code).unwrap(), list);
+    }
+
+    #[test]
+    fn named_map_json_uses_keys_as_names() {
+        let mut destroy = DeckList::new();
+        destroy.set_cards(&["Deadpool", "Carnage"]);
+        let json = format!(
+            r#"{{"Thanos": "{VALID_CODE}", "Destroy": "{}"}}"#,
+            destroy.into_code().unwrap()
+        );
+
+        let decks = from_named_map_json(&json).unwrap();
+        assert_eq!(decks.len(), 2);
+        assert_eq!(decks[0].name(), "Destroy");
+        assert_eq!(decks[0].cards(), vec!["Deadpool", "Carnage"]);
+        assert_eq!(decks[1].name(), "Thanos");
+        assert_eq!(decks[1].cards().len(), 12);
+    }
+
+    #[test]
+    fn named_map_json_reports_failed_key() {
+        let json = format!(r#"{{"Thanos": "{VALID_CODE}", "Broken": "not a code"}}"#);
+        assert!(matches!(
+            from_named_map_json(&json),
+            Err(DeckListError::InvalidEntry { key, .. }) if key == "Broken"
+        ));
+
+        assert!(matches!(
+            from_named_map_json(r#"{"Thanos": 12}"#),
+            Err(DeckListError::InvalidEntry { key, .. }) if key == "Thanos"
+        ));
+        assert!(matches!(
+            from_named_map_json("[]"),
+            Err(DeckListError::InvalidDeckInput)
+        ));
+    }
+
+    #[test]
+    fn named_map_json_round_trip() {
+        let thanos = DeckList::from_code(VALID_CODE).unwrap();
+        let mut other = DeckList::new();
+        other.set_name("Thanos".into());
+        other.set_cards(&["AntMan"]);
+        let mut zoo = DeckList::new();
+        zoo.set_name("Zoo".into());
+        zoo.set_cards(&["Nightcrawler"]);
+
+        let json = to_named_map_json(&[thanos.clone(), other.clone(), zoo.clone()]).unwrap();
+        let decks = from_named_map_json(&json).unwrap();
+
+        assert_eq!(decks.len(), 3);
+        assert_eq!(decks[0], thanos);
+        assert_eq!(decks[1], other.clone_with_name("Thanos (2)"));
+        assert_eq!(decks[2], zoo);
+    }
+
+    #[test]
+    fn canonical_ordering_ignored_by_equality() {
+        let mut list = DeckList::from_code(VALID_CODE).unwrap();
+        list.sort_cards();
+        list.set_canonical_ordering(true);
+
+        assert_eq!(
+            DeckList::from_code(list.into_code().unwrap()).unwrap(),
+            list
+        );
+
+        let mut empty = DeckList::new();
+        empty.set_canonical_ordering(true);
+        assert_eq!(empty, DeckList::new());
+    }
+
+    #[test]
+    fn canonical_ordering_applies_to_every_encoder() {
+        let mut list = DeckList::new();
+        list.set_cards(&["Thanos", "AntMan"]);
+        list.set_canonical_ordering(true);
+        let sorted = r#"[{"CardDefId":"AntMan"},{"CardDefId":"Thanos"}]"#;
+
+        assert!(list.to_minimal_json().unwrap().contains(sorted));
+        assert!(list.into_tagged_json().unwrap().contains(sorted));
+        assert!(list.to_value().unwrap().to_string().contains(sorted));
+        assert_eq!(
+            list.to_flat_json().unwrap(),
+            r#"{"name":"","cards":["AntMan","Thanos"]}"#
+        );
+        assert_eq!(list.into_code().unwrap(), list.canonical_code().unwrap());
+    }
+
+    #[test]
+    fn source_tag_ignored_by_equality() {
+        let mut list = DeckList::from_code(VALID_CODE).unwrap();
+        list.set_source_tag("my-tool");
+
+        assert_eq!(list.into_code().unwrap(), VALID_CODE);
+        assert_eq!(
+            DeckList::from_code(list.into_code().unwrap()).unwrap(),
+            list
+        );
+    }
+
+    #[test]
+    fn card_meta_ignored_by_equality() {
+        let list = DeckList::from_code(VALID_CODE).unwrap();
+        let mut cards = list.clone().into_cards();
+        cards[0].set_meta(CardMeta {
+            favorite: true,
+            notes: "Opening play".into(),
+        });
+        assert_eq!(cards[0], list.clone().into_cards()[0]);
+        assert_ne!(cards[0], cards[1]);
+
+        let mut with_meta = DeckList::new();
+        with_meta.set_name("Thanos".into());
+        with_meta.set_cards_from_cards(cards);
+        assert_eq!(with_meta, list);
+        assert!(with_meta.same_cards(&list));
+        assert!(with_meta.diff(&list).added.is_empty());
+    }
+
+    #[test]
+    fn deck_code_is_normalized() {
+        let plain = DeckCode::new(VALID_CODE).unwrap();
+        let prefixed = DeckCode::new(format!(" MSD:{VALID_CODE}\n")).unwrap();
+
+        assert_eq!(prefixed.as_str(), VALID_CODE);
+        assert_eq!(prefixed.to_string(), VALID_CODE);
+        assert_eq!(prefixed, plain);
+    }
+
+    #[test]
+    fn looks_like_code_filters_candidates() {
+        assert!(looks_like_code(VALID_CODE));
+        assert!(!looks_like_code(&VALID_CODE[..20]));
+        assert!(!looks_like_code(&VALID_CODE.repeat(20)));
+        assert!(!looks_like_code(&format!("MSD:{VALID_CODE}")));
+        assert!(!looks_like_code(&format!(
+            "{} {}",
+            &VALID_CODE[..40],
+            &VALID_CODE[40..]
+        )));
+        assert!(!looks_like_code(
+            &general_purpose::STANDARD_NO_PAD.encode("not a deck but long enough to pass")
+        ));
+    }
+
+    #[test]
+    fn def_id_drops_punctuation() {
+        assert_eq!(to_def_id("Mister Negative's"), "MisterNegatives");
+        assert_eq!(to_def_id("Dr. Doom"), "DrDoom");
+        assert_eq!(to_def_id("  Squirrel_Girl "), "SquirrelGirl");
+    }
+
+    #[test]
+    fn popularity_counts_presence() {
+        let mut first = DeckList::new();
+        first.set_cards(&["AntMan", "Thanos", "Thanos"]);
+        let mut second = DeckList::new();
+        second.set_cards(&["Thanos", "Lockjaw"]);
+        let mut third = DeckList::new();
+        third.set_cards(&["Lockjaw"]);
+
+        let popularity = card_popularity(&[first, second, third]);
+
+        assert_eq!(popularity.len(), 3);
+        assert_eq!(popularity["AntMan"], 1);
+        assert_eq!(popularity["Thanos"], 2);
+        assert_eq!(popularity["Lockjaw"], 2);
     }
 }